@@ -1,3 +1,6 @@
+use std::f32::consts::TAU;
+
+use crate::random::Rng;
 use crate::regions::Point;
 
 pub type CurveFn = Box<dyn Fn(&Point, &Point) -> f32>;
@@ -51,3 +54,58 @@ pub fn radiate_linear(min: f32, max: f32) -> CurveFn {
         apply_ratio(distance, max_distance, min, max)
     })
 }
+
+/// Makes any curve periodic over the canvas by mirroring it, so the value at
+/// each edge matches the value at the opposite edge.
+pub fn tileable(curve: CurveFn) -> CurveFn {
+    Box::new(move |point: &Point, bounds: &Point| {
+        let mirror = |v: i32, max: i32| {
+            let v = v.rem_euclid(max.max(1));
+            match 2 * v < max {
+                true => 2 * v,
+                false => 2 * (max - v),
+            }
+        };
+        let mirrored = Point::new(mirror(point.x, bounds.x), mirror(point.y, bounds.y));
+        curve(&mirrored, bounds)
+    })
+}
+
+/// A sine wave along x that repeats `periods` times across the canvas.
+pub fn wave_x(min: f32, max: f32, periods: u32) -> CurveFn {
+    Box::new(move |point: &Point, bounds: &Point| {
+        let phase = TAU * periods as f32 * point.x as f32 / bounds.x as f32;
+        min + (max - min) * (0.5 - 0.5 * phase.cos())
+    })
+}
+
+/// A sine wave along y that repeats `periods` times across the canvas.
+pub fn wave_y(min: f32, max: f32, periods: u32) -> CurveFn {
+    Box::new(move |point: &Point, bounds: &Point| {
+        let phase = TAU * periods as f32 * point.y as f32 / bounds.y as f32;
+        min + (max - min) * (0.5 - 0.5 * phase.cos())
+    })
+}
+
+/// Smooth value noise with `cells` noise cells along each axis. The lattice
+/// wraps around at the canvas edges, so the noise is always tileable.
+pub fn noise(min: f32, max: f32, cells: u32, seed: u64) -> CurveFn {
+    let cells = cells.max(1) as usize;
+    let mut rng = Rng::new(seed);
+    let lattice: Vec<f32> = (0..cells * cells).map(|_| rng.next_f32()).collect();
+    Box::new(move |point: &Point, bounds: &Point| {
+        let u = point.x as f32 / bounds.x as f32 * cells as f32;
+        let v = point.y as f32 / bounds.y as f32 * cells as f32;
+        let (x0, y0) = (u.floor() as i64, v.floor() as i64);
+        let value = |x: i64, y: i64| {
+            let x = x.rem_euclid(cells as i64) as usize;
+            let y = y.rem_euclid(cells as i64) as usize;
+            lattice[y * cells + x]
+        };
+        let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+        let (tx, ty) = (smooth(u - x0 as f32), smooth(v - y0 as f32));
+        let bottom = value(x0, y0) * (1.0 - tx) + value(x0 + 1, y0) * tx;
+        let top = value(x0, y0 + 1) * (1.0 - tx) + value(x0 + 1, y0 + 1) * tx;
+        min + (max - min) * (bottom * (1.0 - ty) + top * ty)
+    })
+}
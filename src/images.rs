@@ -1,8 +1,10 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::PI;
 
 use crate::image_writer::write;
 
-use crate::regions::{Point, Rectangle, Region};
+use crate::regions::{Point, PointAnnotation, PointFloat, Polygon, Rectangle, Region, Transform};
 
 const R_SHIFT: u32 = 0;
 const G_SHIFT: u32 = 8;
@@ -198,6 +200,12 @@ impl Pixel {
         self
     }
 
+    /// Scales the alpha by the fraction of a pixel a shape covers.
+    pub fn with_coverage(self, coverage: f32) -> Self {
+        let alpha = (self.a as f32 * coverage.clamp(0.0, 1.0)).round() as u8;
+        self.set_alpha(alpha)
+    }
+
     pub fn blend(mut self, other: &Pixel) -> Self {
         let a = 255 - ((255 - self.a as u32) * (255 - other.a as u32) / 255) as u8;
 
@@ -212,6 +220,85 @@ impl Pixel {
         self
     }
 
+    /// The color with the srgb gamma curve removed, so that the values are
+    /// proportional to light intensity, from 0 to 1.
+    pub fn to_linear_rgb(&self) -> [f32; 3] {
+        [self.r, self.g, self.b].map(|c| {
+            let c = c as f32 / 255.0;
+            match c <= 0.04045 {
+                true => c / 12.92,
+                false => ((c + 0.055) / 1.055).powf(2.4),
+            }
+        })
+    }
+
+    pub fn from_linear_rgb(rgb: [f32; 3], a: u8) -> Self {
+        let [r, g, b] = rgb.map(|c| {
+            let c = c.clamp(0.0, 1.0);
+            let c = match c <= 0.0031308 {
+                true => c * 12.92,
+                false => 1.055 * c.powf(1.0 / 2.4) - 0.055,
+            };
+            (c * 255.0).round() as u8
+        });
+        Self::rgba(r, g, b, a)
+    }
+
+    /// The color in the OKLab space, as lightness and two opponent color
+    /// axes. Equal steps in OKLab look like roughly equal changes in color.
+    pub fn to_oklab(&self) -> [f32; 3] {
+        let [r, g, b] = self.to_linear_rgb();
+        let l = (0.4122215 * r + 0.5363325 * g + 0.05144599 * b).cbrt();
+        let m = (0.2119035 * r + 0.6806995 * g + 0.107397 * b).cbrt();
+        let s = (0.08830246 * r + 0.2817188 * g + 0.6299787 * b).cbrt();
+        [
+            0.2104543 * l + 0.7936178 * m - 0.00407205 * s,
+            1.977998 * l - 2.428592 * m + 0.4505937 * s,
+            0.02590404 * l + 0.7827718 * m - 0.8086758 * s,
+        ]
+    }
+
+    pub fn from_oklab(lab: [f32; 3], a: u8) -> Self {
+        let [lightness, lab_a, lab_b] = lab;
+        let l = (lightness + 0.3963378 * lab_a + 0.2158038 * lab_b).powi(3);
+        let m = (lightness - 0.1055613 * lab_a - 0.06385417 * lab_b).powi(3);
+        let s = (lightness - 0.08948418 * lab_a - 1.291486 * lab_b).powi(3);
+        Self::from_linear_rgb(
+            [
+                4.076742 * l - 3.307712 * m + 0.2309699 * s,
+                -1.268438 * l + 2.609757 * m - 0.3413194 * s,
+                -0.00419609 * l - 0.7034186 * m + 1.707615 * s,
+            ],
+            a,
+        )
+    }
+
+    /// Mixes the colors in the given proportions, which should add up to 1.
+    /// Alpha is always mixed linearly.
+    pub fn mix(pixels: &[Pixel], weights: &[f32], space: ColorSpace) -> Pixel {
+        let encoded = pixels.iter().map(|pixel| (space.encode(pixel), pixel.a));
+        Self::mix_encoded(encoded, weights, space)
+    }
+
+    /// Like `mix`, for colors that are already encoded in the space, along
+    /// with their alpha, so that colors mixed again and again are only
+    /// encoded once.
+    pub fn mix_encoded(
+        colors: impl Iterator<Item = ([f32; 3], u8)>,
+        weights: &[f32],
+        space: ColorSpace,
+    ) -> Pixel {
+        let mut values = [0.0; 3];
+        let mut alpha = 0.0;
+        for ((encoded, a), weight) in colors.zip(weights) {
+            for (value, channel) in values.iter_mut().zip(encoded) {
+                *value += channel * weight;
+            }
+            alpha += a as f32 * weight;
+        }
+        space.decode(values, alpha.clamp(0.0, 255.0).round() as u8)
+    }
+
     pub fn blend_multiple_no_alpha(pixels: &[&Pixel]) -> Pixel {
         let a_per = 255 / pixels.len() as u32;
         let mut result = Pixel::new();
@@ -224,10 +311,159 @@ impl Pixel {
     }
 }
 
+/// The space colors are mixed in. Mixing in srgb is what naive averaging of
+/// pixel values does, and makes mixes of bright colors too dark. Linear mixes
+/// like light does, and OKLab keeps the perceived lightness and saturation
+/// of a mix even.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    #[default]
+    Srgb,
+    Linear,
+    OkLab,
+}
+
+impl ColorSpace {
+    pub fn encode(&self, pixel: &Pixel) -> [f32; 3] {
+        match self {
+            ColorSpace::Srgb => [pixel.r, pixel.g, pixel.b].map(|c| c as f32 / 255.0),
+            ColorSpace::Linear => pixel.to_linear_rgb(),
+            ColorSpace::OkLab => pixel.to_oklab(),
+        }
+    }
+
+    pub fn decode(&self, values: [f32; 3], a: u8) -> Pixel {
+        match self {
+            ColorSpace::Srgb => {
+                let [r, g, b] = values.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+                Pixel::rgba(r, g, b, a)
+            }
+            ColorSpace::Linear => Pixel::from_linear_rgb(values, a),
+            ColorSpace::OkLab => Pixel::from_oklab(values, a),
+        }
+    }
+}
+
+/// Colors at positions between 0 and 1, blended in between.
+#[derive(Clone, Debug)]
+pub struct Gradient {
+    stops: Vec<(f32, Pixel)>,
+    space: ColorSpace,
+}
+
+impl Gradient {
+    /// The stops may be given in any order. A gradient without stops is
+    /// transparent everywhere.
+    pub fn new(mut stops: Vec<(f32, Pixel)>) -> Self {
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            stops,
+            space: ColorSpace::Srgb,
+        }
+    }
+
+    /// The colors spread evenly from 0 to 1, in order.
+    pub fn even(colors: &[Pixel]) -> Self {
+        let last = (colors.len() as f32 - 1.0).max(1.0);
+        Self::new(
+            colors
+                .iter()
+                .enumerate()
+                .map(|(i, color)| (i as f32 / last, *color))
+                .collect(),
+        )
+    }
+
+    pub fn color_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// The color at the position. Positions before the first stop or after
+    /// the last take the color of that stop, and positions that aren't a
+    /// number take the color of the first.
+    pub fn at(&self, position: f32) -> Pixel {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Pixel::new(),
+        };
+        if position.is_nan() || position <= first.0 {
+            return first.1;
+        }
+        if position >= last.0 {
+            return last.1;
+        }
+        let next = self
+            .stops
+            .iter()
+            .position(|stop| stop.0 > position)
+            .unwrap();
+        let ((from, start), (to, end)) = (self.stops[next - 1], self.stops[next]);
+        let t = (position - from) / (to - from);
+        Pixel::mix(&[start, end], &[1.0 - t, t], self.space)
+    }
+}
+
+/// How an image is read between the centers of its pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sampling {
+    /// The color of the pixel the point falls in.
+    #[default]
+    Nearest,
+    /// A blend of the four nearest pixels, weighted by how close they are.
+    Bilinear,
+}
+
+/// The filter used to scale a supersampled image down to its output size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Filter {
+    /// The plain average of the pixels that make up an output pixel.
+    Box,
+    /// A weighted average that also reaches into the neighbouring output
+    /// pixels, which softens edges a little more.
+    Tent,
+    /// A windowed sinc with three lobes, which keeps edges sharp but can ring
+    /// slightly around strong contrasts.
+    Lanczos,
+}
+
+impl Filter {
+    /// How far the filter reaches from the center of an output pixel, in
+    /// output pixels.
+    fn radius(&self) -> f32 {
+        match self {
+            Filter::Box => 0.5,
+            Filter::Tent => 1.0,
+            Filter::Lanczos => 3.0,
+        }
+    }
+
+    fn weight(&self, t: f32) -> f32 {
+        let sinc = |x: f32| match x == 0.0 {
+            true => 1.0,
+            false => (PI * x).sin() / (PI * x),
+        };
+        match self {
+            Filter::Box => match t.abs() < 0.5 {
+                true => 1.0,
+                false => 0.0,
+            },
+            Filter::Tent => (1.0 - t.abs()).max(0.0),
+            Filter::Lanczos => match t.abs() < 3.0 {
+                true => sinc(t) * sinc(t / 3.0),
+                false => 0.0,
+            },
+        }
+    }
+}
+
 pub struct Image {
     bounds: Rectangle,
     pub size: Point,
     pub data: Vec<Pixel>,
+    wrap: bool,
+    scale: i32,
+    filter: Filter,
 }
 
 impl Image {
@@ -236,14 +472,220 @@ impl Image {
             bounds: Rectangle::normal(Point::new(width, height)),
             size: Point::new(width, height),
             data: vec![Pixel::new(); (width * height) as usize],
+            wrap: false,
+            scale: 1,
+            filter: Filter::Box,
+        }
+    }
+
+    /// Renders at `scale` times the size of the image and filters it back
+    /// down when it is written. Regions draw once on the larger canvas, so
+    /// anything laid out relative to the size of the image gets the extra
+    /// detail without knowing about it. Shapes at fixed positions are moved
+    /// onto the canvas with `to_canvas`.
+    pub fn supersample(mut self, scale: i32, filter: Filter) -> Self {
+        let scale = scale.max(1);
+        let size = Point::new(self.size.x * scale, self.size.y * scale);
+        self.bounds = Rectangle::normal(size.clone());
+        self.data = vec![Pixel::new(); (size.x * size.y) as usize];
+        self.size = size;
+        self.scale = scale;
+        self.filter = filter;
+        self
+    }
+
+    /// How many pixels of the canvas there are to a pixel of the output along
+    /// each axis, which is 1 unless the image is supersampled.
+    pub fn scale(&self) -> f32 {
+        self.scale as f32
+    }
+
+    /// The transform from the coordinates of the output, which shapes are
+    /// laid out in, to the pixels of the canvas regions draw on.
+    pub fn to_canvas(&self) -> Transform {
+        Transform::scale(self.scale(), self.scale())
+    }
+
+    /// The polygons moved onto the canvas, borrowed as they are unless the
+    /// image is supersampled.
+    pub fn on_canvas<'a>(&self, polygons: &'a [Polygon]) -> Cow<'a, [Polygon]> {
+        if self.scale == 1 {
+            return Cow::Borrowed(polygons);
+        }
+        let transform = self.to_canvas();
+        Cow::Owned(
+            polygons
+                .iter()
+                .map(|polygon| polygon.transform(&transform))
+                .collect(),
+        )
+    }
+
+    /// An image whose coordinates wrap around toroidally: anything drawn past
+    /// one edge reappears on the opposite edge, so the output tiles without a
+    /// seam.
+    pub fn tileable(width: i32, height: i32) -> Self {
+        let mut result = Self::new(width, height);
+        result.wrap = true;
+        result
+    }
+
+    pub fn is_tileable(&self) -> bool {
+        self.wrap
+    }
+
+    fn wrap_point(&self, point: &Point) -> Point {
+        match self.wrap {
+            true => Point::new(
+                point.x.rem_euclid(self.size.x),
+                point.y.rem_euclid(self.size.y),
+            )
+            .annotate(point.annotation.clone()),
+            false => point.clone(),
         }
     }
 
     pub fn get_pixel(&self, point: &Point) -> &Pixel {
-        &self.data[point.to_linear(self.size.x) as usize]
+        &self.data[self.wrap_point(point).to_linear(self.size.x) as usize]
+    }
+
+    /// For every output pixel along one axis, the pixels of the supersampled
+    /// image that contribute to it and their share.
+    fn filter_taps(&self, output_len: i32) -> Vec<Vec<(i32, f32)>> {
+        let scale = self.scale as f32;
+        let input_len = output_len * self.scale;
+        let radius = self.filter.radius() * scale;
+        (0..output_len)
+            .map(|i| {
+                let center = (i as f32 + 0.5) * scale;
+                let first = (center - radius).floor() as i32;
+                let last = (center + radius).ceil() as i32;
+                let mut taps: Vec<(i32, f32)> = (first..last)
+                    .map(|j| {
+                        let index = match self.wrap {
+                            true => j.rem_euclid(input_len),
+                            false => j.clamp(0, input_len - 1),
+                        };
+                        (index, self.filter.weight((j as f32 + 0.5 - center) / scale))
+                    })
+                    .filter(|(_, weight)| *weight != 0.0)
+                    .collect();
+                let total: f32 = taps.iter().map(|(_, weight)| weight).sum();
+                for (_, weight) in taps.iter_mut() {
+                    *weight /= total;
+                }
+                taps
+            })
+            .collect()
+    }
+
+    /// Filters a supersampled image down to its output size. Colors are
+    /// weighted by their alpha, so transparent pixels do not bleed their
+    /// color into the edges of what is drawn over them.
+    pub fn downsample(&self) -> Image {
+        let size = Point::new(self.size.x / self.scale, self.size.y / self.scale);
+        let mut result = Image::new(size.x, size.y);
+        result.wrap = self.wrap;
+        if self.scale == 1 {
+            result.data = self.data.clone();
+            return result;
+        }
+        // premultiplied red, green, blue and alpha, followed by the plain
+        // colors for pixels that end up completely transparent
+        let channels = |pixel: &Pixel| {
+            let alpha = pixel.a as f32 / 255.0;
+            [
+                pixel.r as f32 * alpha,
+                pixel.g as f32 * alpha,
+                pixel.b as f32 * alpha,
+                pixel.a as f32,
+                pixel.r as f32,
+                pixel.g as f32,
+                pixel.b as f32,
+            ]
+        };
+
+        let columns = self.filter_taps(size.x);
+        let mut horizontal = vec![[0.0; 7]; (size.x * self.size.y) as usize];
+        for y in 0..self.size.y {
+            for (x, taps) in columns.iter().enumerate() {
+                let sum = &mut horizontal[(y * size.x) as usize + x];
+                for (source_x, weight) in taps {
+                    let values = channels(&self.data[(y * self.size.x + source_x) as usize]);
+                    for (channel, value) in sum.iter_mut().zip(values) {
+                        *channel += value * weight;
+                    }
+                }
+            }
+        }
+
+        let rows = self.filter_taps(size.y);
+        for (y, taps) in rows.iter().enumerate() {
+            for x in 0..size.x {
+                let mut sum = [0.0; 7];
+                for (source_y, weight) in taps {
+                    let values = horizontal[(source_y * size.x + x) as usize];
+                    for (channel, value) in sum.iter_mut().zip(values) {
+                        *channel += value * weight;
+                    }
+                }
+                let alpha = sum[3].clamp(0.0, 255.0);
+                let color = match alpha >= 0.5 {
+                    true => [sum[0], sum[1], sum[2]].map(|c| c * 255.0 / alpha),
+                    false => [sum[4], sum[5], sum[6]],
+                }
+                .map(|c| c.clamp(0.0, 255.0).round() as u8);
+                result.data[y * size.x as usize + x as usize] =
+                    Pixel::rgba(color[0], color[1], color[2], alpha.round() as u8);
+            }
+        }
+        result
+    }
+
+    /// Reads the color at any point of the image, in pixels. Points outside
+    /// of the image take the color of the nearest edge, unless the image is
+    /// tileable and wraps around.
+    pub fn sample(&self, point: &PointFloat, sampling: Sampling) -> Pixel {
+        let texel = |x: i32, y: i32| match self.wrap {
+            true => *self.get_pixel(&Point::new(x, y)),
+            false => *self.get_pixel(&Point::new(
+                x.clamp(0, self.size.x - 1),
+                y.clamp(0, self.size.y - 1),
+            )),
+        };
+        match sampling {
+            Sampling::Nearest => texel(point.x.floor() as i32, point.y.floor() as i32),
+            Sampling::Bilinear => {
+                // the four pixels whose centers surround the point
+                let (x, y) = (point.x - 0.5, point.y - 0.5);
+                let (x0, y0) = (x.floor(), y.floor());
+                let (tx, ty) = (x - x0, y - y0);
+                let (x0, y0) = (x0 as i32, y0 as i32);
+                Pixel::mix(
+                    &[
+                        texel(x0, y0),
+                        texel(x0 + 1, y0),
+                        texel(x0, y0 + 1),
+                        texel(x0 + 1, y0 + 1),
+                    ],
+                    &[
+                        (1.0 - tx) * (1.0 - ty),
+                        tx * (1.0 - ty),
+                        (1.0 - tx) * ty,
+                        tx * ty,
+                    ],
+                    ColorSpace::Srgb,
+                )
+            }
+        }
     }
 
+    /// Writes the image as a png, filtered down to its output size first if
+    /// it is supersampled.
     pub fn write(&self, name: &str) {
+        if self.scale > 1 {
+            return self.downsample().write(name);
+        }
         let output_data = self
             .data
             .iter()
@@ -253,21 +695,64 @@ impl Image {
     }
 
     pub fn contains(&self, point: &Point) -> bool {
-        self.bounds.contains(point)
+        self.wrap || self.bounds.contains(point)
     }
 
+    /// Collects the pixels a region would draw onto this image without
+    /// applying them. Later mutations of the same point win.
+    pub fn sample_region(&self, region: &dyn Region) -> HashMap<Point, Pixel> {
+        let mut mutations: Vec<(Point, Pixel)> = Vec::new();
+        region.get_mutations(self, &mut mutations);
+        mutations
+            .into_iter()
+            .map(|(point, pixel)| (Point::new(point.x, point.y), pixel))
+            .collect()
+    }
+
+    /// Draws the region onto the image. Pixels replace what is there, and a
+    /// point drawn twice is marked black. Pixels a shape only partly covers,
+    /// like the edges of anti aliased shapes, are composited over the image
+    /// instead. The partial pixels a region draws on the same point are
+    /// summed first, so that two shapes sharing an edge add up to an opaque
+    /// pixel without the background showing through the seam.
     pub fn apply_region(&mut self, region: &dyn Region) {
         let dup_color = Pixel::rgba(0, 0, 0, 0xff);
         let mut all_points: HashSet<Point> = HashSet::new();
+        // premultiplied red, green, blue and alpha
+        let mut partial: HashMap<Point, [f32; 4]> = HashMap::new();
         let mut mutations: Vec<(Point, Pixel)> = Vec::new();
         region.get_mutations(self, &mut mutations);
         for (point, pixel) in mutations {
-            if all_points.contains(&point) {
+            let point = self.wrap_point(&point);
+            if point.annotation == PointAnnotation::Partial {
+                let alpha = pixel.a as f32 / 255.0;
+                let sum = partial
+                    .entry(Point::new(point.x, point.y))
+                    .or_insert([0.0; 4]);
+                sum[0] += pixel.r as f32 * alpha;
+                sum[1] += pixel.g as f32 * alpha;
+                sum[2] += pixel.b as f32 * alpha;
+                sum[3] += alpha;
+            } else if all_points.contains(&point) {
                 self.data[point.to_linear(self.size.x) as usize].copy_from(&dup_color);
             } else {
                 all_points.insert(point.clone());
                 self.data[point.to_linear(self.size.x) as usize].copy_from(&pixel);
             }
         }
+        for (point, [r, g, b, alpha]) in partial {
+            if alpha <= 0.0 {
+                continue;
+            }
+            let pixel = Pixel::rgba(
+                (r / alpha).round() as u8,
+                (g / alpha).round() as u8,
+                (b / alpha).round() as u8,
+                (alpha.min(1.0) * 255.0).round() as u8,
+            );
+            let index = point.to_linear(self.size.x) as usize;
+            let blended = self.data[index].blend(&pixel);
+            self.data[index].copy_from(&blended);
+        }
     }
 }
@@ -1,4 +1,4 @@
-use crate::images::Image;
+use crate::images::{Filter, Image};
 use regions::{Point, Region};
 
 const BASE_PATH: &str = "/mnt/c/Users/isaac/Pictures/patterns/";
@@ -7,7 +7,9 @@ mod curves;
 mod image_writer;
 mod images;
 mod macros;
+mod palette;
 mod polygons;
+mod random;
 mod regions;
 mod test_shapes;
 mod transformers;
@@ -17,6 +19,29 @@ fn main() {
     // apply_regions("02_TestTessellation", test_shapes::test_tessellation());
     // apply_regions("03_TestPrimitives", test_shapes::test_primitives());
     // apply_regions("04_TestGradient", test_shapes::test_gradient());
+    // apply_regions("05_TestLowPoly", test_shapes::test_low_poly());
+    // apply_regions("06_TestTruchet", test_shapes::test_truchet());
+    // apply_regions("07_TestWallpaper", test_shapes::test_wallpaper());
+    // apply_regions("08_TestKaleidoscope", test_shapes::test_kaleidoscope());
+    // apply_tileable_regions("09_TestTileable", test_shapes::test_tileable());
+    // apply_regions("10_TestCompound", test_shapes::test_compound());
+    // apply_regions("11_TestBoolean", test_shapes::test_boolean());
+    // apply_regions("12_TestCircles", test_shapes::test_circles());
+    // apply_regions("13_TestStars", test_shapes::test_stars());
+    // apply_regions("14_TestStrokes", test_shapes::test_strokes());
+    // apply_regions("15_TestPaths", test_shapes::test_paths());
+    // apply_regions("16_TestTransforms", test_shapes::test_transforms());
+    // apply_regions("17_TestSubpixel", test_shapes::test_subpixel());
+    // apply_regions("18_TestAntiAlias", test_shapes::test_anti_alias());
+    // apply_supersampled_regions("19_TestSupersample", test_shapes::test_supersample(), Filter::Lanczos);
+    // apply_regions("20_TestShading", test_shapes::test_shading());
+    // apply_regions("21_TestTextures", test_shapes::test_textures());
+    // apply_regions("22_TestSdf", test_shapes::test_sdf());
+    // apply_regions("23_TestTileChannels", test_shapes::test_tile_channels());
+    // apply_regions("24_TestBorders", test_shapes::test_borders());
+    // apply_regions("25_TestSampledTiles", test_shapes::test_sampled_tiles());
+    // apply_regions("26_TestAverages", test_shapes::test_averages());
+    // apply_regions("27_TestPalette", test_shapes::test_palette());
 }
 
 fn apply_regions(name: &str, rns: Vec<Box<dyn Region>>) {
@@ -28,3 +53,22 @@ fn apply_regions(name: &str, rns: Vec<Box<dyn Region>>) {
         image.write(format!("{}/{}_{:02}.png", BASE_PATH, name, i).as_str());
     }
 }
+
+fn apply_tileable_regions(name: &str, scene: (Point, Vec<Box<dyn Region>>)) {
+    let (bounds, rns) = scene;
+    let mut image = Image::tileable(bounds.x, bounds.y);
+    for (i, region) in rns.iter().enumerate() {
+        image.apply_region(region.as_ref());
+        image.write(format!("{}/{}_{:02}.png", BASE_PATH, name, i).as_str());
+    }
+}
+
+fn apply_supersampled_regions(name: &str, scene: (i32, Vec<Box<dyn Region>>), filter: Filter) {
+    let (scale, rns) = scene;
+    let bounds = Point::new(600, 800);
+    let mut image = Image::new(bounds.x, bounds.y).supersample(scale, filter);
+    for (i, region) in rns.iter().enumerate() {
+        image.apply_region(region.as_ref());
+        image.write(format!("{}/{}_{:02}.png", BASE_PATH, name, i).as_str());
+    }
+}
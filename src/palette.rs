@@ -0,0 +1,370 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::images::{ColorSpace, Image, Pixel};
+use crate::random::Rng;
+
+/// How the colors of many pixels are summed up in a single color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Average {
+    /// The mean in linear light, the color the pixels blur into when seen
+    /// from far away.
+    #[default]
+    Mean,
+    /// The median of every channel, which ignores small details in another
+    /// color.
+    Median,
+    /// The most common color, with close colors counted as the same.
+    Mode,
+    /// The center of the largest of the given number of clusters of similar
+    /// colors, the dominant color even when the rest of the colors vary.
+    KMeans(u32),
+}
+
+impl Average {
+    /// The color that stands for all of the pixels. An empty slice gives a
+    /// transparent pixel.
+    pub fn of(&self, pixels: &[Pixel]) -> Pixel {
+        if pixels.is_empty() {
+            return Pixel::new();
+        }
+        match self {
+            Average::Mean => mean(pixels, ColorSpace::Linear),
+            Average::Median => median(pixels),
+            Average::Mode => mode(pixels),
+            Average::KMeans(k) => kmeans(pixels, *k as usize, ColorSpace::OkLab)[0].0,
+        }
+    }
+}
+
+/// How a palette of a few colors is picked out of all of the colors of an
+/// image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Quantizer {
+    /// Splits the colors in half at the median of their widest channel,
+    /// over and over. Fast, and gives every palette color about the same
+    /// share of the pixels.
+    #[default]
+    MedianCut,
+    /// Clusters of similar colors in OKLab, which follow the colors people
+    /// see best, but takes the longest.
+    KMeans,
+    /// Sorts the colors into a tree of ever smaller cubes and merges the
+    /// smallest cubes until the palette is small enough.
+    Octree,
+}
+
+impl Quantizer {
+    /// At most `colors` colors that stand for the image, the most common
+    /// first, and none at all for 0. Transparent pixels are left out.
+    pub fn palette(&self, image: &Image, colors: usize) -> Vec<Pixel> {
+        let pixels: Vec<Pixel> = image.data.iter().filter(|p| p.a > 0).copied().collect();
+        self.palette_of(&pixels, colors)
+    }
+
+    pub fn palette_of(&self, pixels: &[Pixel], colors: usize) -> Vec<Pixel> {
+        if colors == 0 {
+            return vec![];
+        }
+        match self {
+            Quantizer::MedianCut => median_cut(pixels, colors),
+            Quantizer::KMeans => kmeans(pixels, colors, ColorSpace::OkLab)
+                .into_iter()
+                .map(|(color, _)| color)
+                .collect(),
+            Quantizer::Octree => octree(pixels, colors),
+        }
+    }
+}
+
+/// The mean of the colors mixed in the given space, weighted by their
+/// alpha so that nearly transparent pixels barely count.
+pub fn mean(pixels: &[Pixel], space: ColorSpace) -> Pixel {
+    let mut values = [0.0; 3];
+    let mut total_alpha = 0.0;
+    for pixel in pixels {
+        let weight = pixel.a as f32;
+        for (value, channel) in values.iter_mut().zip(space.encode(pixel)) {
+            *value += channel * weight;
+        }
+        total_alpha += weight;
+    }
+    if total_alpha == 0.0 {
+        // all of them are transparent, so count them equally
+        let weights = vec![1.0 / pixels.len() as f32; pixels.len()];
+        return Pixel::mix(pixels, &weights, space);
+    }
+    let alpha = (total_alpha / pixels.len() as f32).round() as u8;
+    space.decode(values.map(|value| value / total_alpha), alpha)
+}
+
+/// The median of every channel on its own.
+pub fn median(pixels: &[Pixel]) -> Pixel {
+    let middle = |channel: fn(&Pixel) -> u8| {
+        let mut values: Vec<u8> = pixels.iter().map(channel).collect();
+        let half = values.len() / 2;
+        *values.select_nth_unstable(half).1
+    };
+    Pixel::rgba(
+        middle(|p| p.r),
+        middle(|p| p.g),
+        middle(|p| p.b),
+        middle(|p| p.a),
+    )
+}
+
+/// The mean of the most common group of colors, where colors that only
+/// differ in the lowest 4 bits of every channel are grouped together.
+pub fn mode(pixels: &[Pixel]) -> Pixel {
+    let mut groups: HashMap<(u8, u8, u8), Vec<Pixel>> = HashMap::new();
+    let mut order = vec![];
+    for pixel in pixels {
+        let key = (pixel.r >> 4, pixel.g >> 4, pixel.b >> 4);
+        let group = groups.entry(key).or_insert_with(|| {
+            order.push(key);
+            vec![]
+        });
+        group.push(*pixel);
+    }
+    // the first group seen wins ties, so that the result doesn't depend on
+    // the order of the map
+    let mut largest = &groups[&order[0]];
+    for key in order.iter().skip(1) {
+        if groups[key].len() > largest.len() {
+            largest = &groups[key];
+        }
+    }
+    mean(largest, ColorSpace::Linear)
+}
+
+fn distance_sq(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum()
+}
+
+/// The index of the color closest to `value`, with all colors encoded in the
+/// same space.
+pub fn nearest(centers: &[[f32; 3]], value: &[f32; 3]) -> usize {
+    let mut best = 0;
+    for (i, center) in centers.iter().enumerate().skip(1) {
+        if distance_sq(center, value) < distance_sq(&centers[best], value) {
+            best = i;
+        }
+    }
+    best
+}
+
+/// Groups the colors into at most `k` clusters of similar colors, measured
+/// in the given space. Returns the mean color of every cluster with the
+/// number of pixels in it, largest first.
+pub fn kmeans(pixels: &[Pixel], k: usize, space: ColorSpace) -> Vec<(Pixel, usize)> {
+    let values: Vec<[f32; 3]> = pixels.iter().map(|pixel| space.encode(pixel)).collect();
+    let k = k.clamp(1, values.len().max(1));
+    if values.is_empty() {
+        return vec![];
+    }
+
+    // spread the first centers out, picking each next one with a chance
+    // that grows with its distance to the centers so far (k-means++)
+    let mut rng = Rng::new(values.len() as u64);
+    let mut centers = vec![values[rng.index(values.len())]];
+    let mut distances: Vec<f32> = values.iter().map(|v| distance_sq(v, &centers[0])).collect();
+    while centers.len() < k {
+        let total: f32 = distances.iter().sum();
+        if total == 0.0 {
+            break;
+        }
+        let mut target = rng.next_f32() * total;
+        let mut chosen = values.len() - 1;
+        for (i, distance) in distances.iter().enumerate() {
+            target -= distance;
+            if target <= 0.0 && *distance > 0.0 {
+                chosen = i;
+                break;
+            }
+        }
+        centers.push(values[chosen]);
+        for (distance, value) in distances.iter_mut().zip(values.iter()) {
+            *distance = distance.min(distance_sq(value, &values[chosen]));
+        }
+    }
+
+    let mut assignments = vec![usize::MAX; values.len()];
+    for _ in 0..20 {
+        let mut changed = false;
+        for (assignment, value) in assignments.iter_mut().zip(values.iter()) {
+            let index = nearest(&centers, value);
+            changed |= *assignment != index;
+            *assignment = index;
+        }
+        if !changed {
+            break;
+        }
+        let mut sums = vec![[0.0; 3]; centers.len()];
+        let mut counts = vec![0; centers.len()];
+        for (index, value) in assignments.iter().zip(values.iter()) {
+            for (sum, channel) in sums[*index].iter_mut().zip(value) {
+                *sum += channel;
+            }
+            counts[*index] += 1;
+        }
+        for ((center, sum), count) in centers.iter_mut().zip(sums).zip(counts) {
+            // a center that lost all of its pixels stays where it is
+            if count > 0 {
+                *center = sum.map(|channel| channel / count as f32);
+            }
+        }
+    }
+
+    let mut clusters: Vec<Vec<Pixel>> = vec![vec![]; centers.len()];
+    for (index, pixel) in assignments.iter().zip(pixels) {
+        clusters[*index].push(*pixel);
+    }
+    let mut result: Vec<(Pixel, usize)> = clusters
+        .iter()
+        .filter(|cluster| !cluster.is_empty())
+        .map(|cluster| (mean(cluster, space), cluster.len()))
+        .collect();
+    result.sort_by_key(|(_, size)| Reverse(*size));
+    result
+}
+
+/// Splits the colors into at most `colors` boxes, each time cutting the box
+/// with the widest range in any channel in half at its median. Returns the
+/// mean color of every box, largest first.
+pub fn median_cut(pixels: &[Pixel], colors: usize) -> Vec<Pixel> {
+    let channels: [fn(&Pixel) -> u8; 3] = [|p| p.r, |p| p.g, |p| p.b];
+    let widest = |pixels: &[Pixel]| {
+        channels
+            .iter()
+            .map(|channel| {
+                let values = pixels.iter().map(channel);
+                let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+                (*channel, range)
+            })
+            .max_by_key(|(_, range)| *range)
+            .unwrap()
+    };
+    if colors == 0 {
+        return vec![];
+    }
+    let mut boxes: Vec<Vec<Pixel>> = vec![pixels.to_vec()];
+    while boxes.len() < colors {
+        let (index, (channel, range)) = match boxes
+            .iter()
+            .enumerate()
+            .map(|(i, pixels)| (i, widest(pixels)))
+            .max_by_key(|(_, (_, range))| *range)
+        {
+            Some(widest) => widest,
+            None => break,
+        };
+        if range == 0 {
+            break;
+        }
+        let mut lower = boxes.swap_remove(index);
+        lower.sort_unstable_by_key(channel);
+        let upper = lower.split_off(lower.len() / 2);
+        boxes.push(lower);
+        boxes.push(upper);
+    }
+    boxes.retain(|pixels| !pixels.is_empty());
+    boxes.sort_by_key(|pixels| Reverse(pixels.len()));
+    boxes
+        .iter()
+        .map(|pixels| mean(pixels, ColorSpace::Linear))
+        .collect()
+}
+
+/// A cube of colors in the octree, which counts the colors inside of it.
+struct OctreeNode {
+    children: [Option<usize>; 8],
+    parent: usize,
+    count: u64,
+    level: usize,
+    is_leaf: bool,
+}
+
+/// Sorts the colors into a tree where every level halves the cubes along
+/// each channel, then merges the cubes with the fewest pixels into their
+/// parents, deepest first, until at most `colors` cubes are left. Returns
+/// the mean color of every cube in linear light, largest first.
+pub fn octree(pixels: &[Pixel], colors: usize) -> Vec<Pixel> {
+    const DEPTH: usize = 8;
+    if colors == 0 {
+        return vec![];
+    }
+    let new_node = |level: usize, parent: usize| OctreeNode {
+        children: [None; 8],
+        parent,
+        count: 0,
+        level,
+        is_leaf: level == DEPTH,
+    };
+    let mut nodes = vec![new_node(0, 0)];
+    // the deepest cube of every pixel
+    let mut deepest = Vec::with_capacity(pixels.len());
+    for pixel in pixels {
+        let mut index = 0;
+        for level in 0..=DEPTH {
+            nodes[index].count += 1;
+            if level == DEPTH {
+                break;
+            }
+            let bit = 7 - level;
+            let octant = ((pixel.r >> bit & 1) << 2
+                | (pixel.g >> bit & 1) << 1
+                | pixel.b >> bit & 1) as usize;
+            index = match nodes[index].children[octant] {
+                Some(child) => child,
+                None => {
+                    nodes.push(new_node(level + 1, index));
+                    nodes[index].children[octant] = Some(nodes.len() - 1);
+                    nodes.len() - 1
+                }
+            };
+        }
+        deepest.push(index);
+    }
+
+    let mut leaves = nodes.iter().filter(|node| node.is_leaf).count();
+    // every node already counts the colors of its cube, so merging its
+    // children only turns it into a leaf. The deepest cubes are merged
+    // first, so all children are leaves by the time their level is reached.
+    for level in (0..DEPTH).rev() {
+        if leaves <= colors {
+            break;
+        }
+        let mut parents: Vec<usize> = (0..nodes.len())
+            .filter(|index| nodes[*index].level == level)
+            .collect();
+        parents.sort_by_key(|index| nodes[*index].count);
+        for index in parents {
+            if leaves <= colors {
+                break;
+            }
+            let children: Vec<usize> = nodes[index].children.iter().flatten().copied().collect();
+            for child in children.iter() {
+                nodes[*child].is_leaf = false;
+                nodes[*child].count = 0;
+            }
+            nodes[index].children = [None; 8];
+            nodes[index].is_leaf = true;
+            leaves = leaves + 1 - children.len();
+        }
+    }
+
+    let mut cubes: Vec<Vec<Pixel>> = vec![vec![]; nodes.len()];
+    for (pixel, mut index) in pixels.iter().zip(deepest) {
+        // the cube a pixel ended up in is the first of its cubes still left
+        while !nodes[index].is_leaf {
+            index = nodes[index].parent;
+        }
+        cubes[index].push(*pixel);
+    }
+    cubes.retain(|pixels| !pixels.is_empty());
+    cubes.sort_by_key(|pixels| Reverse(pixels.len()));
+    cubes
+        .iter()
+        .map(|pixels| mean(pixels, ColorSpace::Linear))
+        .collect()
+}
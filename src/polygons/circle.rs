@@ -0,0 +1,97 @@
+use crate::regions::{Conic, Point, PointFloat, Polygon, Rectangle};
+
+pub struct Circle {}
+
+impl Circle {
+    pub fn new(center: &Point, radius: f32) -> Polygon {
+        Polygon::from_conic(Conic::circle(center.to_float(), radius))
+    }
+
+    /// Polka dots: circles on a grid with the given spacing, with every other
+    /// row shifted by half a step.
+    pub fn tessellate(
+        center: &Point,
+        radius: f32,
+        spacing: i32,
+        bounds: &Rectangle,
+    ) -> Vec<Polygon> {
+        let mut result = Vec::new();
+        let ext = bounds.extent();
+        let start_x = bounds.origin.x + (center.x - bounds.origin.x).rem_euclid(spacing);
+        let start_y = bounds.origin.y + (center.y - bounds.origin.y).rem_euclid(spacing);
+
+        let mut y = start_y - spacing;
+        while y <= ext.y + spacing {
+            let skew = match ((center.y - y) / spacing) % 2 != 0 {
+                true => spacing / 2,
+                false => 0,
+            };
+            let mut x = start_x - spacing + skew;
+            while x <= ext.x + spacing {
+                result.push(Self::new(&Point::new(x, y), radius));
+                x += spacing;
+            }
+            y += spacing;
+        }
+        result
+    }
+}
+
+pub struct Ellipse {}
+
+impl Ellipse {
+    /// An ellipse rotated by the given angle, in degrees.
+    pub fn new(center: &Point, radius_x: f32, radius_y: f32, rotation: f32) -> Polygon {
+        let radii = PointFloat::new(radius_x, radius_y);
+        Polygon::from_conic(Conic::ellipse(center.to_float(), radii).rotate(rotation))
+    }
+}
+
+pub struct Sector {}
+
+impl Sector {
+    /// A pie slice from `start` to `start + sweep`, in degrees.
+    pub fn new(center: &Point, radius: f32, start: f32, sweep: f32) -> Polygon {
+        Polygon::from_conic(Conic::circle(center.to_float(), radius).wedge(start, sweep))
+    }
+}
+
+pub struct ArcBand {}
+
+impl ArcBand {
+    /// The part of a ring between two angles, in degrees. Bands without an
+    /// outer radius are empty.
+    pub fn new(center: &Point, inner: f32, outer: f32, start: f32, sweep: f32) -> Polygon {
+        if outer <= 0.0 {
+            return Polygon::from_shapes(vec![], vec![]);
+        }
+        Polygon::from_conic(
+            Conic::circle(center.to_float(), outer)
+                .hollow(inner / outer)
+                .wedge(start, sweep),
+        )
+    }
+}
+
+pub struct Annulus {}
+
+impl Annulus {
+    /// A ring between two radii. Rings without an outer radius are empty.
+    pub fn new(center: &Point, inner: f32, outer: f32) -> Polygon {
+        if outer <= 0.0 {
+            return Polygon::from_shapes(vec![], vec![]);
+        }
+        Polygon::from_conic(Conic::circle(center.to_float(), outer).hollow(inner / outer))
+    }
+
+    /// Concentric rings of the given width around the center, from the
+    /// innermost outwards. The innermost ring is a full circle.
+    pub fn concentric(center: &Point, width: f32, count: u32) -> Vec<Polygon> {
+        (0..count)
+            .map(|i| match i {
+                0 => Circle::new(center, width),
+                _ => Self::new(center, width * i as f32, width * (i + 1) as f32),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+
+use crate::images::Image;
+use crate::random::Rng;
+use crate::regions::{Point, Polygon, Rectangle};
+
+struct DelaunayTriangle {
+    vertices: [usize; 3],
+    center: (f64, f64),
+    radius_square: f64,
+}
+
+impl DelaunayTriangle {
+    fn new(vertices: [usize; 3], points: &[(f64, f64)]) -> Self {
+        let (ax, ay) = points[vertices[0]];
+        let (bx, by) = points[vertices[1]];
+        let (cx, cy) = points[vertices[2]];
+        let d = 2.0 * (ax * (by - cy) + bx * (cy - ay) + cx * (ay - by));
+        let a_sq = ax * ax + ay * ay;
+        let b_sq = bx * bx + by * by;
+        let c_sq = cx * cx + cy * cy;
+        let ux = (a_sq * (by - cy) + b_sq * (cy - ay) + c_sq * (ay - by)) / d;
+        let uy = (a_sq * (cx - bx) + b_sq * (ax - cx) + c_sq * (bx - ax)) / d;
+        Self {
+            vertices,
+            center: (ux, uy),
+            radius_square: (ax - ux).powi(2) + (ay - uy).powi(2),
+        }
+    }
+
+    fn circumcircle_contains(&self, point: (f64, f64)) -> bool {
+        (point.0 - self.center.0).powi(2) + (point.1 - self.center.1).powi(2) < self.radius_square
+    }
+
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+fn sorted_edge(edge: (usize, usize)) -> (usize, usize) {
+    (edge.0.min(edge.1), edge.0.max(edge.1))
+}
+
+fn is_degenerate(p1: &Point, p2: &Point, p3: &Point) -> bool {
+    let cross =
+        (p2.x - p1.x) as i64 * (p3.y - p1.y) as i64 - (p2.y - p1.y) as i64 * (p3.x - p1.x) as i64;
+    cross == 0
+}
+
+pub struct Delaunay {}
+
+impl Delaunay {
+    /// Bowyer-Watson triangulation of the given points. Duplicate points are
+    /// ignored and degenerate (zero area) triangles are never returned.
+    pub fn triangulate(points: &[Point]) -> Vec<[Point; 3]> {
+        let mut seen = HashSet::new();
+        let unique: Vec<Point> = points
+            .iter()
+            .map(|p| Point::new(p.x, p.y))
+            .filter(|p| seen.insert(p.clone()))
+            .collect();
+        if unique.len() < 3 {
+            return vec![];
+        }
+
+        let mut coords: Vec<(f64, f64)> = unique.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+        let min_x = coords.iter().map(|c| c.0).fold(f64::MAX, f64::min);
+        let min_y = coords.iter().map(|c| c.1).fold(f64::MAX, f64::min);
+        let max_x = coords.iter().map(|c| c.0).fold(f64::MIN, f64::max);
+        let max_y = coords.iter().map(|c| c.1).fold(f64::MIN, f64::max);
+        let span = (max_x - min_x).max(max_y - min_y).max(1.0);
+        let mid = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+
+        // super triangle, large enough that it never influences the result. A
+        // smaller one drops hull triangles when many points lie on the hull,
+        // like the points along the edges of the canvas
+        let super_start = coords.len();
+        coords.push((mid.0 - 1000.0 * span, mid.1 - span));
+        coords.push((mid.0, mid.1 + 1000.0 * span));
+        coords.push((mid.0 + 1000.0 * span, mid.1 - span));
+
+        let mut triangles = vec![DelaunayTriangle::new(
+            [super_start, super_start + 1, super_start + 2],
+            &coords,
+        )];
+
+        for index in 0..super_start {
+            let point = coords[index];
+            let (bad, good): (Vec<DelaunayTriangle>, Vec<DelaunayTriangle>) = triangles
+                .into_iter()
+                .partition(|tri| tri.circumcircle_contains(point));
+            triangles = good;
+
+            // the boundary of the hole is every edge used by exactly one bad triangle
+            let mut boundary: Vec<(usize, usize)> = vec![];
+            for tri in bad.iter() {
+                for edge in tri.edges() {
+                    let shared = bad.iter().any(|other| {
+                        !std::ptr::eq(other, tri)
+                            && other
+                                .edges()
+                                .iter()
+                                .any(|e| sorted_edge(*e) == sorted_edge(edge))
+                    });
+                    if !shared {
+                        boundary.push(edge);
+                    }
+                }
+            }
+
+            for (a, b) in boundary {
+                triangles.push(DelaunayTriangle::new([a, b, index], &coords));
+            }
+        }
+
+        triangles
+            .iter()
+            .filter(|tri| tri.vertices.iter().all(|v| *v < super_start))
+            .map(|tri| {
+                [
+                    unique[tri.vertices[0]].clone(),
+                    unique[tri.vertices[1]].clone(),
+                    unique[tri.vertices[2]].clone(),
+                ]
+            })
+            .filter(|[p1, p2, p3]| !is_degenerate(p1, p2, p3))
+            .collect()
+    }
+
+    pub fn tessellate(points: &[Point]) -> Vec<Polygon> {
+        Self::triangulate(points)
+            .into_iter()
+            .map(|[p1, p2, p3]| Polygon::triangle(p1, p2, p3))
+            .collect()
+    }
+
+    pub fn corners(bounds: &Rectangle) -> Vec<Point> {
+        let ext = bounds.extent();
+        vec![
+            bounds.origin.clone(),
+            Point::new(ext.x, bounds.origin.y),
+            Point::new(bounds.origin.x, ext.y),
+            ext,
+        ]
+    }
+
+    pub fn random_points(bounds: &Rectangle, count: usize, seed: u64) -> Vec<Point> {
+        let mut rng = Rng::new(seed);
+        let ext = bounds.extent();
+        let mut result = Self::corners(bounds);
+        for _ in 0..count {
+            result.push(Point::new(
+                rng.range(bounds.origin.x, ext.x + 1),
+                rng.range(bounds.origin.y, ext.y + 1),
+            ));
+        }
+        result
+    }
+
+    /// A grid of points `spacing` apart, each moved at random by up to half
+    /// of the spacing. Spacings below 1 are treated as 1.
+    pub fn jittered_grid_points(bounds: &Rectangle, spacing: i32, seed: u64) -> Vec<Point> {
+        let spacing = spacing.max(1);
+        let mut rng = Rng::new(seed);
+        let ext = bounds.extent();
+        let jitter = spacing / 2;
+        let mut result = Self::corners(bounds);
+        let mut y = bounds.origin.y;
+        while y <= ext.y {
+            let mut x = bounds.origin.x;
+            while x <= ext.x {
+                result.push(Point::new(
+                    (x + rng.range(-jitter, jitter + 1)).clamp(bounds.origin.x, ext.x),
+                    (y + rng.range(-jitter, jitter + 1)).clamp(bounds.origin.y, ext.y),
+                ));
+                x += spacing;
+            }
+            y += spacing;
+        }
+        result
+    }
+
+    /// Samples points with a density proportional to `weight_fn`, which must
+    /// return a value between 0 and 1 for every point in the image.
+    fn weighted_points(
+        image: &Image,
+        count: usize,
+        seed: u64,
+        weight_fn: impl Fn(&Point) -> f32,
+    ) -> Vec<Point> {
+        let mut rng = Rng::new(seed);
+        let bounds = Rectangle::normal(Point::new(image.size.x - 1, image.size.y - 1));
+        let mut result = Self::corners(&bounds);
+        let mut attempts = 0;
+        let max_attempts = count * 100;
+        while result.len() < count + 4 && attempts < max_attempts {
+            attempts += 1;
+            let point = Point::new(rng.range(0, image.size.x), rng.range(0, image.size.y));
+            if rng.next_f32() < weight_fn(&point) {
+                result.push(point);
+            }
+        }
+        result
+    }
+
+    /// Samples points more densely in the darker parts of the image.
+    pub fn sampled_points(image: &Image, count: usize, seed: u64) -> Vec<Point> {
+        Self::weighted_points(image, count, seed, |point| {
+            let (_, _, l, _) = image.get_pixel(point).to_hsla();
+            (1.0 - l).max(0.05)
+        })
+    }
+
+    /// Samples points along strong changes in lightness so that triangle
+    /// edges follow the edges in the image.
+    pub fn edge_points(image: &Image, count: usize, seed: u64) -> Vec<Point> {
+        let lightness = |x: i32, y: i32| {
+            let point = Point::new(x.clamp(0, image.size.x - 1), y.clamp(0, image.size.y - 1));
+            image.get_pixel(&point).to_hsla().2
+        };
+        Self::weighted_points(image, count, seed, |point| {
+            let (x, y) = (point.x, point.y);
+            // sobel operator
+            let gx = lightness(x + 1, y - 1) + 2.0 * lightness(x + 1, y) + lightness(x + 1, y + 1)
+                - lightness(x - 1, y - 1)
+                - 2.0 * lightness(x - 1, y)
+                - lightness(x - 1, y + 1);
+            let gy = lightness(x - 1, y + 1) + 2.0 * lightness(x, y + 1) + lightness(x + 1, y + 1)
+                - lightness(x - 1, y - 1)
+                - 2.0 * lightness(x, y - 1)
+                - lightness(x + 1, y - 1);
+            (gx * gx + gy * gy).sqrt().clamp(0.02, 1.0)
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use crate::regions::{Orientation, Point, Polygon, Rectangle, StandardTriangle};
+use crate::regions::{Orientation, Point, Polygon, Rectangle, Triangle};
 
 pub const ROOT_3: f64 = 1.732;
 
@@ -24,27 +24,27 @@ impl Hexagon {
             Point::new(max_x - min_x, mid_y_p - mid_y_n),
         );
 
-        let t_top_left = StandardTriangle::new(
+        let t_top_left = Triangle::new_standard(
             Point::new(min_x, mid_y_p),
             Point::new(center.x, mid_y_p),
             Point::new(center.x, max_y),
             Orientation::Up,
         );
 
-        let t_top_right = StandardTriangle::new(
+        let t_top_right = Triangle::new_standard(
             Point::new(center.x, mid_y_p),
             Point::new(max_x, mid_y_p),
             Point::new(center.x, max_y),
             Orientation::Up,
         );
 
-        let t_bot_left = StandardTriangle::new(
+        let t_bot_left = Triangle::new_standard(
             Point::new(min_x, mid_y_n),
             Point::new(center.x, mid_y_n),
             Point::new(center.x, min_y),
             Orientation::Down,
         );
-        let t_bot_right = StandardTriangle::new(
+        let t_bot_right = Triangle::new_standard(
             Point::new(center.x, mid_y_n),
             Point::new(max_x, mid_y_n),
             Point::new(center.x, min_y),
@@ -98,6 +98,30 @@ impl Hexagon {
         result
     }
 
+    /// Returns a canvas size that a grid of hexagons with the given side length
+    /// repeats over, with `columns` hexagons per row and `rows` pairs of rows.
+    pub fn tileable_size(side_len: i32, columns: i32, rows: i32) -> Point {
+        let step = Self::regular_step(side_len);
+        Point::new(step.x * columns, step.y * 2 * rows)
+    }
+
+    /// Tessellates a tileable canvas (see `tileable_size`) with exactly one
+    /// hexagon per grid position. Hexagons that cross the top or right edge are
+    /// expected to wrap around on a tileable `Image`.
+    pub fn tessellate_tileable(side_len: i32, size: &Point) -> Vec<Polygon> {
+        let step = Self::regular_step(side_len);
+        let polygon_bounds = Self::regular_bounds(side_len);
+        let mut result = Vec::new();
+        for row in 0..size.y / step.y {
+            for col in 0..size.x / step.x {
+                let mut bounds = polygon_bounds.clone();
+                bounds.origin = Point::new(col * step.x + (row % 2) * (step.x / 2), row * step.y);
+                result.push(Self::from_bounds(&bounds));
+            }
+        }
+        result
+    }
+
     pub fn tessellation_center_fn(
         poly_index: &Point,
         normalized_bounds: &Rectangle,
@@ -123,9 +147,12 @@ impl Hexagon {
         Rectangle::new(origin, normalized_bounds.size.clone())
     }
 
+    /// The width is rounded to an even number, so that the rows of a
+    /// tessellation, which are shifted by half a hexagon, share their edges
+    /// exactly.
     pub fn regular_bounds(side_len: i32) -> Rectangle {
         Rectangle::normal(Point::new(
-            (ROOT_3 * side_len as f64).round() as i32,
+            (ROOT_3 * side_len as f64 / 2.0).round() as i32 * 2,
             2 * side_len,
         ))
     }
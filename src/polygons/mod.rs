@@ -1,5 +1,13 @@
+mod circle;
+mod delaunay;
 mod hexagon;
 mod rectangle;
+mod regular;
+mod truchet;
 
+pub use circle::{Annulus, ArcBand, Circle, Ellipse, Sector};
+pub use delaunay::Delaunay;
 pub use hexagon::Hexagon;
 pub use rectangle::RectanglePoly;
+pub use regular::{RegularPolygon, Star};
+pub use truchet::{Truchet, TruchetStyle, Wang};
@@ -13,6 +13,25 @@ impl RectanglePoly {
         Polygon::from_shapes(vec![], vec![rect])
     }
 
+    /// A rectangular frame: the bounding box with a border of the given
+    /// width, and a hole in the middle.
+    pub fn frame(bbox: &Rectangle, border: i32) -> Polygon {
+        let ext = bbox.extent();
+        let outline = vec![
+            bbox.origin.clone(),
+            Point::new(ext.x, bbox.origin.y),
+            ext.clone(),
+            Point::new(bbox.origin.x, ext.y),
+        ];
+        let hole = vec![
+            Point::new(bbox.origin.x + border, bbox.origin.y + border),
+            Point::new(ext.x - border, bbox.origin.y + border),
+            Point::new(ext.x - border, ext.y - border),
+            Point::new(bbox.origin.x + border, ext.y - border),
+        ];
+        Polygon::with_holes(&outline, &[hole])
+    }
+
     pub fn tessellate(
         center: &Point,
         size: &Point,
@@ -60,6 +79,26 @@ impl RectanglePoly {
         result
     }
 
+    /// Tessellates a tileable canvas with exactly one rectangle per grid
+    /// position. The canvas size should be a multiple of the rectangle size,
+    /// and of twice its height when the rows are offset.
+    pub fn tessellate_tileable(size: &Point, canvas: &Point, offset: bool) -> Vec<Polygon> {
+        let mut result = Vec::new();
+        for row in 0..canvas.y / size.y {
+            let skew = match offset && row % 2 != 0 {
+                true => size.x / 2,
+                false => 0,
+            };
+            for col in 0..canvas.x / size.x {
+                result.push(Self::new(
+                    Point::new(col * size.x + skew, row * size.y),
+                    size.clone(),
+                ));
+            }
+        }
+        result
+    }
+
     pub fn regular_bounds(side_len: i32) -> Rectangle {
         Rectangle::normal(Point::new(side_len, side_len))
     }
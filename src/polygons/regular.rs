@@ -0,0 +1,100 @@
+use crate::regions::{CompoundPath, FillRule, Point, PointFloat, Polygon, Rectangle};
+
+/// Returns `count` points evenly spaced on a circle, the first one pointing
+/// up (along +y) before the rotation, in degrees, is applied.
+fn circle_points(center: &PointFloat, radius: f32, count: u32, rotation: f32) -> Vec<PointFloat> {
+    (0..count)
+        .map(|i| {
+            let angle = (90.0 + rotation + 360.0 * i as f32 / count as f32).to_radians();
+            PointFloat::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+pub struct RegularPolygon {}
+
+impl RegularPolygon {
+    pub fn vertices(center: &Point, radius: f32, sides: u32, rotation: f32) -> Vec<PointFloat> {
+        circle_points(&center.to_float(), radius, sides.max(3), rotation)
+    }
+
+    pub fn new(center: &Point, radius: f32, sides: u32, rotation: f32) -> Polygon {
+        let outline = Self::vertices(center, radius, sides, rotation);
+        Polygon::from_path(CompoundPath::new(vec![outline], FillRule::NonZero))
+    }
+
+    /// A regular polygon stretched to fill the bounds, the way
+    /// `Hexagon::from_bounds` fills them with a hexagon.
+    pub fn from_bounds(bounds: &Rectangle, sides: u32, rotation: f32) -> Polygon {
+        let unit = circle_points(&PointFloat::new(0.0, 0.0), 1.0, sides.max(3), rotation);
+        let min_x = unit.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+        let max_x = unit.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+        let min_y = unit.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        let max_y = unit.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        let outline = unit
+            .iter()
+            .map(|p| {
+                PointFloat::new(
+                    bounds.origin.x as f32 + (p.x - min_x) / (max_x - min_x) * bounds.size.x as f32,
+                    bounds.origin.y as f32 + (p.y - min_y) / (max_y - min_y) * bounds.size.y as f32,
+                )
+            })
+            .collect();
+        Polygon::from_path(CompoundPath::new(vec![outline], FillRule::NonZero))
+    }
+}
+
+pub struct Star {}
+
+impl Star {
+    /// A star with `points` tips on the outer radius and the notches between
+    /// them on the inner radius.
+    pub fn new(center: &Point, outer: f32, inner: f32, points: u32, rotation: f32) -> Polygon {
+        let points = points.max(2);
+        let center = center.to_float();
+        let tips = circle_points(&center, outer, points, rotation);
+        let notches = circle_points(&center, inner, points, rotation + 180.0 / points as f32);
+        let outline = tips
+            .into_iter()
+            .zip(notches)
+            .flat_map(|(tip, notch)| [tip, notch])
+            .collect();
+        Polygon::from_path(CompoundPath::new(vec![outline], FillRule::NonZero))
+    }
+
+    /// The star polygon {n/k}: n points on a circle, each joined to the point
+    /// k steps further along. When n and k share a divisor the star is made of
+    /// several overlapping polygons, like the hexagram {6/2}. `NonZero` fills
+    /// the whole star, `EvenOdd` leaves the overlapping center empty.
+    pub fn schlafli(
+        center: &Point,
+        radius: f32,
+        n: u32,
+        k: u32,
+        rotation: f32,
+        fill_rule: FillRule,
+    ) -> Polygon {
+        let n = n.max(3);
+        let k = k.clamp(1, n - 1);
+        let points = circle_points(&center.to_float(), radius, n, rotation);
+        let parts = gcd(n, k);
+        let contours = (0..parts)
+            .map(|start| {
+                (0..n / parts)
+                    .map(|i| points[((start + i * k) % n) as usize])
+                    .collect()
+            })
+            .collect();
+        Polygon::from_path(CompoundPath::new(contours, fill_rule))
+    }
+}
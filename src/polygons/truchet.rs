@@ -0,0 +1,237 @@
+use std::f32::consts::FRAC_PI_2;
+
+use crate::random::Rng;
+use crate::regions::{Point, Polygon, Rectangle, Triangle};
+
+use super::RectanglePoly;
+
+const ARC_SEGMENTS: i32 = 8;
+
+#[derive(Clone, Copy, Debug)]
+pub enum TruchetStyle {
+    /// Two quarter circle bands around opposite corners.
+    Arcs,
+    /// A single band connecting opposite corners.
+    Diagonals,
+    /// The half of the square on one side of a diagonal.
+    Triangles,
+}
+
+fn push_triangle(triangles: &mut Vec<Triangle>, p1: Point, p2: Point, p3: Point) {
+    let cross =
+        (p2.x - p1.x) as i64 * (p3.y - p1.y) as i64 - (p2.y - p1.y) as i64 * (p3.x - p1.x) as i64;
+    if cross != 0 {
+        triangles.push(Triangle::new(p1, p2, p3));
+    }
+}
+
+/// Returns the corners of the cell in clockwise order, starting at the
+/// corner selected by the rotation.
+fn rotated_corners(bounds: &Rectangle, rotation: u8) -> [Point; 4] {
+    let ext = bounds.extent();
+    let corners = [
+        bounds.origin.clone(),
+        Point::new(ext.x, bounds.origin.y),
+        ext.clone(),
+        Point::new(bounds.origin.x, ext.y),
+    ];
+    let r = rotation as usize % 4;
+    [
+        corners[r].clone(),
+        corners[(r + 1) % 4].clone(),
+        corners[(r + 2) % 4].clone(),
+        corners[(r + 3) % 4].clone(),
+    ]
+}
+
+/// Returns the grid index of every cell in the tessellation, relative to the
+/// first cell.
+fn cell_indices(cells: &[Polygon], size: i32) -> Vec<Point> {
+    if cells.is_empty() {
+        return vec![];
+    }
+    let first = cells[0].bounds().origin;
+    cells
+        .iter()
+        .map(|cell| {
+            let origin = cell.bounds().origin;
+            Point::new((origin.x - first.x) / size, (origin.y - first.y) / size)
+        })
+        .collect()
+}
+
+pub struct Truchet {}
+
+impl Truchet {
+    pub fn tile(bounds: &Rectangle, style: TruchetStyle, rotation: u8, line_width: i32) -> Polygon {
+        let mut triangles = vec![];
+        let corners = rotated_corners(bounds, rotation);
+        match style {
+            TruchetStyle::Arcs => {
+                let radius = bounds.size.x as f32 / 2.0;
+                let inner = (radius - line_width as f32 / 2.0).max(0.0);
+                let outer = radius + line_width as f32 / 2.0;
+                // the arcs sit on the first and third corner, each sweeping
+                // the quarter that points into the cell
+                for (corner, next) in [(&corners[0], &corners[1]), (&corners[2], &corners[3])] {
+                    let start = ((next.y - corner.y) as f32).atan2((next.x - corner.x) as f32);
+                    let sweep = FRAC_PI_2;
+                    let arc_point = |radius: f32, step: i32| {
+                        let angle = start + sweep * step as f32 / ARC_SEGMENTS as f32;
+                        Point::new(
+                            corner.x + (radius * angle.cos()).round() as i32,
+                            corner.y + (radius * angle.sin()).round() as i32,
+                        )
+                    };
+                    for step in 0..ARC_SEGMENTS {
+                        let in_1 = arc_point(inner, step);
+                        let in_2 = arc_point(inner, step + 1);
+                        let out_1 = arc_point(outer, step);
+                        let out_2 = arc_point(outer, step + 1);
+                        push_triangle(&mut triangles, in_1.clone(), out_1, out_2.clone());
+                        push_triangle(&mut triangles, in_1, out_2, in_2);
+                    }
+                }
+            }
+            TruchetStyle::Diagonals => {
+                let [start, right, end, left] = corners;
+                let offset = ((line_width as f32) / std::f32::consts::SQRT_2).round() as i32;
+                let step = |from: &Point, to: &Point| {
+                    Point::new(
+                        from.x + offset * (to.x - from.x).signum(),
+                        from.y + offset * (to.y - from.y).signum(),
+                    )
+                };
+                let outline = [
+                    start.clone(),
+                    step(&start, &right),
+                    step(&end, &right),
+                    end.clone(),
+                    step(&end, &left),
+                    step(&start, &left),
+                ];
+                for i in 1..outline.len() - 1 {
+                    push_triangle(
+                        &mut triangles,
+                        outline[0].clone(),
+                        outline[i].clone(),
+                        outline[i + 1].clone(),
+                    );
+                }
+            }
+            TruchetStyle::Triangles => {
+                let [p1, p2, _, p4] = corners;
+                push_triangle(&mut triangles, p1, p2, p4);
+            }
+        }
+        Polygon::from_triangles(triangles)
+    }
+
+    /// Fills the square grid from `RectanglePoly::tessellate` with tiles of the
+    /// given style, each rotated at random.
+    pub fn tessellate(
+        center: &Point,
+        size: i32,
+        bounds: &Rectangle,
+        style: TruchetStyle,
+        line_width: i32,
+        seed: u64,
+    ) -> Vec<Polygon> {
+        let mut rng = Rng::new(seed);
+        RectanglePoly::tessellate(center, &Point::new(size, size), bounds, false)
+            .iter()
+            .map(|cell| Self::tile(&cell.bounds(), style, rng.range(0, 4) as u8, line_width))
+            .collect()
+    }
+}
+
+pub struct Wang {}
+
+impl Wang {
+    /// Draws a tile of the two color Wang tile set. Each edge flag (north,
+    /// east, south, west) connects the middle of that edge to the center of
+    /// the tile with a band of the given width.
+    pub fn tile(bounds: &Rectangle, edges: [bool; 4], line_width: i32) -> Polygon {
+        let center = bounds.center();
+        let half = line_width / 2;
+        let hub = Rectangle::new(
+            Point::new(center.x - half, center.y - half),
+            Point::new(line_width, line_width),
+        );
+        let hub_ext = hub.extent();
+        let ext = bounds.extent();
+        let [north, east, south, west] = edges;
+
+        let mut rectangles = vec![];
+        if north {
+            rectangles.push(Rectangle::from_bounds(
+                Point::new(hub.origin.x, bounds.origin.y),
+                Point::new(hub_ext.x, hub.origin.y),
+            ));
+        }
+        if east {
+            rectangles.push(Rectangle::from_bounds(
+                Point::new(hub_ext.x, hub.origin.y),
+                Point::new(ext.x, hub_ext.y),
+            ));
+        }
+        if south {
+            rectangles.push(Rectangle::from_bounds(
+                Point::new(hub.origin.x, hub_ext.y),
+                Point::new(hub_ext.x, ext.y),
+            ));
+        }
+        if west {
+            rectangles.push(Rectangle::from_bounds(
+                Point::new(bounds.origin.x, hub.origin.y),
+                Point::new(hub.origin.x, hub_ext.y),
+            ));
+        }
+        if !rectangles.is_empty() {
+            rectangles.push(hub);
+        }
+        Polygon::from_shapes(vec![], rectangles)
+    }
+
+    /// Picks a random color for every edge of the grid so that neighboring
+    /// tiles always agree on the edge they share. Tiles without any connected
+    /// edge are left out.
+    pub fn tessellate(
+        center: &Point,
+        size: i32,
+        bounds: &Rectangle,
+        line_width: i32,
+        seed: u64,
+    ) -> Vec<Polygon> {
+        let mut rng = Rng::new(seed);
+        let cells = RectanglePoly::tessellate(center, &Point::new(size, size), bounds, false);
+        let indices = cell_indices(&cells, size);
+        let cols = indices.iter().map(|i| i.x).max().unwrap_or(0) as usize + 1;
+        let rows = indices.iter().map(|i| i.y).max().unwrap_or(0) as usize + 1;
+
+        // horizontal_edges[row][col] is the edge above cell (col, row)
+        let horizontal_edges: Vec<Vec<bool>> = (0..=rows)
+            .map(|_| (0..cols).map(|_| rng.chance(0.5)).collect())
+            .collect();
+        // vertical_edges[row][col] is the edge left of cell (col, row)
+        let vertical_edges: Vec<Vec<bool>> = (0..rows)
+            .map(|_| (0..=cols).map(|_| rng.chance(0.5)).collect())
+            .collect();
+
+        cells
+            .iter()
+            .zip(indices.iter())
+            .map(|(cell, index)| {
+                let (col, row) = (index.x as usize, index.y as usize);
+                let edges = [
+                    horizontal_edges[row][col],
+                    vertical_edges[row][col + 1],
+                    horizontal_edges[row + 1][col],
+                    vertical_edges[row][col],
+                ];
+                Self::tile(&cell.bounds(), edges, line_width)
+            })
+            .filter(|tile| tile.bounds().size.x > 0)
+            .collect()
+    }
+}
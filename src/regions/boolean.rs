@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use super::{point::PointFloat, CompoundPath, FillRule, Polygon};
+
+const EPSILON: f32 = 1e-3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    Difference,
+    Xor,
+}
+
+impl BooleanOp {
+    fn apply(&self, in_a: bool, in_b: bool) -> bool {
+        match self {
+            BooleanOp::Union => in_a || in_b,
+            BooleanOp::Intersection => in_a && in_b,
+            BooleanOp::Difference => in_a && !in_b,
+            BooleanOp::Xor => in_a != in_b,
+        }
+    }
+}
+
+fn key(point: &PointFloat) -> (i64, i64) {
+    (
+        (point.x / EPSILON).round() as i64,
+        (point.y / EPSILON).round() as i64,
+    )
+}
+
+struct Segment {
+    start: PointFloat,
+    end: PointFloat,
+    // split points along the segment, by their position from start to end
+    splits: Vec<(f32, PointFloat)>,
+}
+
+impl Segment {
+    fn new(start: PointFloat, end: PointFloat) -> Self {
+        Self {
+            splits: vec![(0.0, start), (1.0, end)],
+            start,
+            end,
+        }
+    }
+
+    /// Returns the position of the point along the segment if it lies on the
+    /// segment, strictly between its ends.
+    fn position_of(&self, point: &PointFloat) -> Option<f32> {
+        let dir = self.end - self.start;
+        let len_sq = dir.dot(&dir);
+        let rel = *point - self.start;
+        let t = rel.dot(&dir) / len_sq;
+        let distance = dir.cross(&rel).abs() / len_sq.sqrt();
+        match distance < EPSILON && t > 0.0 && t < 1.0 {
+            true => Some(t),
+            false => None,
+        }
+    }
+}
+
+fn split_segments(segments: &mut [Segment]) {
+    for i in 0..segments.len() {
+        for j in (i + 1)..segments.len() {
+            let (left, right) = segments.split_at_mut(j);
+            let (a, b) = (&mut left[i], &mut right[0]);
+            let r = a.end - a.start;
+            let s = b.end - b.start;
+            let denom = r.cross(&s);
+            let rel = b.start - a.start;
+
+            if denom.abs() > EPSILON * EPSILON {
+                let t = rel.cross(&s) / denom;
+                let u = rel.cross(&r) / denom;
+                if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+                    let point = a.start.lerp(&a.end, t);
+                    a.splits.push((t, point));
+                    b.splits.push((u, point));
+                }
+            }
+
+            // touching or overlapping ends are split at the exact end point
+            for point in [&b.start, &b.end] {
+                if let Some(t) = a.position_of(point) {
+                    a.splits.push((t, *point));
+                }
+            }
+            for point in [&a.start, &a.end] {
+                if let Some(u) = b.position_of(point) {
+                    b.splits.push((u, *point));
+                }
+            }
+        }
+    }
+}
+
+/// Joins directed edges that meet end to start into closed outlines.
+fn chain(edges: Vec<(PointFloat, PointFloat)>) -> Vec<Vec<PointFloat>> {
+    let mut by_start: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (index, (start, _)) in edges.iter().enumerate() {
+        by_start.entry(key(start)).or_default().push(index);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut result = vec![];
+
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+        let start_key = key(&edges[first].0);
+        let mut contour = vec![edges[first].0];
+        let mut current = first;
+        loop {
+            let end = &edges[current].1;
+            if key(end) == start_key {
+                break;
+            }
+            contour.push(*end);
+            let next = by_start
+                .get(&key(end))
+                .and_then(|candidates| candidates.iter().find(|i| !used[**i]));
+            match next {
+                Some(next) => {
+                    used[*next] = true;
+                    current = *next;
+                }
+                None => break,
+            }
+        }
+        if contour.len() >= 3 {
+            result.push(contour);
+        }
+    }
+    result
+}
+
+/// Combines two polygons into a new polygon outline. Every edge of either
+/// polygon is split where it meets another edge, and the pieces that separate
+/// the inside of the result from the outside are joined back into outlines,
+/// each with the inside on its left.
+pub fn combine(a: &Polygon, b: &Polygon, op: BooleanOp) -> Polygon {
+    let (a, b) = (a.flattened(), b.flattened());
+    let mut segments: Vec<Segment> = a
+        .contours()
+        .iter()
+        .chain(b.contours().iter())
+        .flat_map(|contour| {
+            contour
+                .iter()
+                .zip(contour.iter().cycle().skip(1))
+                .take(contour.len())
+                .map(|(start, end)| Segment::new(*start, *end))
+                .collect::<Vec<Segment>>()
+        })
+        .filter(|segment| key(&segment.start) != key(&segment.end))
+        .collect();
+    split_segments(&mut segments);
+
+    let mut seen = HashMap::new();
+    let mut edges = vec![];
+    for segment in segments.iter_mut() {
+        segment.splits.sort_by(|a, b| a.0.total_cmp(&b.0));
+        for pair in segment.splits.windows(2) {
+            let (start, end) = (&pair[0].1, &pair[1].1);
+            if key(start) == key(end) {
+                continue;
+            }
+            let normal = (*end - *start).normalize().perpendicular() * (EPSILON * 4.0);
+            let mid = start.lerp(end, 0.5);
+            let left = mid + normal;
+            let right = mid - normal;
+            let in_left = op.apply(a.contains(&left), b.contains(&left));
+            let in_right = op.apply(a.contains(&right), b.contains(&right));
+            if in_left == in_right {
+                continue;
+            }
+            let edge = match in_left {
+                true => (*start, *end),
+                false => (*end, *start),
+            };
+            // overlapping edges of the two polygons would otherwise be kept twice
+            if seen.insert((key(&edge.0), key(&edge.1)), ()).is_none() {
+                edges.push(edge);
+            }
+        }
+    }
+
+    Polygon::from_path(CompoundPath::new(chain(edges), FillRule::NonZero))
+}
@@ -0,0 +1,223 @@
+use super::{point::PointFloat, Point, Rectangle, Transform};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is inside when a ray from it crosses the outlines an odd
+    /// number of times.
+    EvenOdd,
+    /// A point is inside when the outlines wind around it at least once, in
+    /// either direction.
+    NonZero,
+}
+
+impl FillRule {
+    pub fn is_inside(&self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// Reverses the contour if it runs clockwise.
+pub(super) fn counter_clockwise(mut contour: Vec<PointFloat>) -> Vec<PointFloat> {
+    let mut area = 0.0;
+    for i in 0..contour.len() {
+        let (a, b) = (&contour[i], &contour[(i + 1) % contour.len()]);
+        area += a.x * b.y - b.x * a.y;
+    }
+    if area < 0.0 {
+        contour.reverse();
+    }
+    contour
+}
+
+/// Any number of closed outlines filled together under a fill rule. Outlines
+/// may overlap, nest inside each other to form holes, or be disjoint.
+/// Pixels are inside when their center is inside.
+#[derive(Clone, Debug)]
+pub struct CompoundPath {
+    contours: Vec<Vec<PointFloat>>,
+    fill_rule: FillRule,
+}
+
+impl CompoundPath {
+    pub fn new(contours: Vec<Vec<PointFloat>>, fill_rule: FillRule) -> Self {
+        let contours = contours.into_iter().filter(|c| c.len() >= 3).collect();
+        Self {
+            contours,
+            fill_rule,
+        }
+    }
+
+    pub fn from_points(contours: &[Vec<Point>], fill_rule: FillRule) -> Self {
+        Self::new(
+            contours
+                .iter()
+                .map(|contour| contour.iter().map(|p| p.to_float()).collect())
+                .collect(),
+            fill_rule,
+        )
+    }
+
+    pub fn transform(&self, transform: &Transform) -> Self {
+        let contours = self
+            .contours
+            .iter()
+            .map(|contour| contour.iter().map(|p| transform.apply(p)).collect())
+            .collect();
+        Self::new(contours, self.fill_rule)
+    }
+
+    pub fn contours(&self) -> &Vec<Vec<PointFloat>> {
+        &self.contours
+    }
+
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contours.is_empty()
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (&PointFloat, &PointFloat)> {
+        self.contours.iter().flat_map(|contour| {
+            contour
+                .iter()
+                .zip(contour.iter().cycle().skip(1))
+                .take(contour.len())
+        })
+    }
+
+    pub fn bounds(&self) -> Rectangle {
+        let mut min = PointFloat::new(f32::MAX, f32::MAX);
+        let mut max = PointFloat::new(f32::MIN, f32::MIN);
+        for point in self.contours.iter().flatten() {
+            min = PointFloat::new(min.x.min(point.x), min.y.min(point.y));
+            max = PointFloat::new(max.x.max(point.x), max.y.max(point.y));
+        }
+        if self.contours.is_empty() {
+            return Rectangle::normal(Point::new(0, 0));
+        }
+        Rectangle::from_bounds(
+            Point::new(min.x.floor() as i32, min.y.floor() as i32),
+            Point::new(max.x.ceil() as i32, max.y.ceil() as i32),
+        )
+    }
+
+    /// Returns the signed number of times the outlines wind around the point.
+    pub fn winding(&self, point: &PointFloat) -> i32 {
+        self.crossings(point.y)
+            .iter()
+            .filter(|(x, _)| *x <= point.x)
+            .map(|(_, direction)| direction)
+            .sum()
+    }
+
+    pub fn contains(&self, point: &PointFloat) -> bool {
+        self.fill_rule.is_inside(self.winding(point))
+    }
+
+    /// Returns where the outlines cross the horizontal line at `y`, sorted by
+    /// x, along with the direction of each crossing.
+    fn crossings(&self, y: f32) -> Vec<(f32, i32)> {
+        let mut result = vec![];
+        for (a, b) in self.edges() {
+            let direction = if a.y <= y && b.y > y {
+                1
+            } else if b.y <= y && a.y > y {
+                -1
+            } else {
+                continue;
+            };
+            let x = a.x + (y - a.y) * (b.x - a.x) / (b.y - a.y);
+            result.push((x, direction));
+        }
+        result.sort_by(|a, b| a.0.total_cmp(&b.0));
+        result
+    }
+
+    /// Returns the half open ranges of pixel columns in a row whose centers
+    /// are inside.
+    fn row_spans(&self, y: i32) -> Vec<(i32, i32)> {
+        let mut spans = vec![];
+        let mut winding = 0;
+        let mut span_start = 0.0;
+        for (x, direction) in self.crossings(y as f32 + 0.5) {
+            let was_inside = self.fill_rule.is_inside(winding);
+            winding += direction;
+            let is_inside = self.fill_rule.is_inside(winding);
+            if !was_inside && is_inside {
+                span_start = x;
+            } else if was_inside && !is_inside {
+                let start = (span_start - 0.5).ceil() as i32;
+                let end = (x - 0.5).ceil() as i32;
+                if end > start {
+                    spans.push((start, end));
+                }
+            }
+        }
+        spans
+    }
+
+    pub fn iter_points<'a>(&'a self) -> CompoundPathIterator<'a> {
+        let bounds = self.bounds();
+        let mut result = CompoundPathIterator {
+            path: self,
+            y: bounds.origin.y,
+            max_y: bounds.extent().y,
+            spans: vec![],
+            span_index: 0,
+            x: 0,
+        };
+        result.load_row();
+        result
+    }
+}
+
+pub struct CompoundPathIterator<'a> {
+    path: &'a CompoundPath,
+    y: i32,
+    max_y: i32,
+    spans: Vec<(i32, i32)>,
+    span_index: usize,
+    x: i32,
+}
+
+impl<'a> CompoundPathIterator<'a> {
+    fn load_row(&mut self) {
+        while self.y < self.max_y {
+            self.spans = self.path.row_spans(self.y);
+            self.span_index = 0;
+            if let Some((start, _)) = self.spans.first() {
+                self.x = *start;
+                return;
+            }
+            self.y += 1;
+        }
+    }
+}
+
+impl<'a> Iterator for CompoundPathIterator<'a> {
+    type Item = Point;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.max_y {
+            return None;
+        }
+        let result = Point::new(self.x, self.y);
+
+        self.x += 1;
+        if self.x >= self.spans[self.span_index].1 {
+            self.span_index += 1;
+            if self.span_index < self.spans.len() {
+                self.x = self.spans[self.span_index].0;
+            } else {
+                self.y += 1;
+                self.load_row();
+            }
+        }
+
+        Some(result)
+    }
+}
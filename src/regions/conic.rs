@@ -0,0 +1,205 @@
+use std::f32::consts::TAU;
+
+use super::{point::PointFloat, Point, Rectangle, Transform};
+
+const FLATTEN_SEGMENTS: usize = 64;
+
+/// An ellipse, optionally with an elliptical hole in the middle and
+/// optionally limited to the wedge between two angles. This covers circles,
+/// ellipses, sectors, arc bands and annuli. Pixels are inside when their
+/// center is inside. Any affine transform of such a shape is again a conic,
+/// so the shape also keeps the transform that was applied to it.
+#[derive(Clone, Debug)]
+pub struct Conic {
+    center: PointFloat,
+    radii: PointFloat,
+    rotation: f32,
+    inner: f32,
+    start_angle: f32,
+    sweep: f32,
+    transform: Transform,
+    /// The inverse of `transform`, or `None` when the shape was flattened
+    /// into a line and covers nothing.
+    inverse: Option<Transform>,
+}
+
+impl Conic {
+    pub fn ellipse(center: PointFloat, radii: PointFloat) -> Self {
+        Self {
+            center,
+            radii,
+            rotation: 0.0,
+            inner: 0.0,
+            start_angle: 0.0,
+            sweep: TAU,
+            transform: Transform::identity(),
+            inverse: Some(Transform::identity()),
+        }
+    }
+
+    pub fn circle(center: PointFloat, radius: f32) -> Self {
+        Self::ellipse(center, PointFloat::new(radius, radius))
+    }
+
+    /// Rotates the shape around its center, in degrees.
+    pub fn rotate(mut self, degrees: f32) -> Self {
+        self.rotation = degrees.to_radians();
+        self
+    }
+
+    /// Cuts a hole into the shape, as a fraction of its radii.
+    pub fn hollow(mut self, inner: f32) -> Self {
+        self.inner = inner.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Limits the shape to the wedge from `start` to `start + sweep`, in
+    /// degrees, counter clockwise from the x axis.
+    pub fn wedge(mut self, start: f32, sweep: f32) -> Self {
+        self.start_angle = start.to_radians();
+        self.sweep = sweep.to_radians().clamp(0.0, TAU);
+        self
+    }
+
+    /// Applies the transform after any transform the shape already has.
+    pub fn transform(&self, transform: &Transform) -> Self {
+        let combined = self.transform.then(transform);
+        Self {
+            transform: combined,
+            inverse: combined.invert(),
+            ..self.clone()
+        }
+    }
+
+    /// Maps a point into the shape's own frame, where the ellipse is the unit
+    /// circle.
+    fn to_unit(&self, point: &PointFloat) -> (f32, f32) {
+        let point = match &self.inverse {
+            Some(inverse) => inverse.apply(point),
+            None => return (f32::INFINITY, f32::INFINITY),
+        };
+        let (dx, dy) = (point.x - self.center.x, point.y - self.center.y);
+        let (sin, cos) = self.rotation.sin_cos();
+        let x = dx * cos + dy * sin;
+        let y = -dx * sin + dy * cos;
+        (x / self.radii.x, y / self.radii.y)
+    }
+
+    fn from_unit(&self, x: f32, y: f32) -> PointFloat {
+        let (x, y) = (x * self.radii.x, y * self.radii.y);
+        let (sin, cos) = self.rotation.sin_cos();
+        self.transform.apply(&PointFloat::new(
+            self.center.x + x * cos - y * sin,
+            self.center.y + x * sin + y * cos,
+        ))
+    }
+
+    pub fn contains(&self, point: &PointFloat) -> bool {
+        let (x, y) = self.to_unit(point);
+        let dist_sq = x * x + y * y;
+        if dist_sq > 1.0 || dist_sq < self.inner * self.inner {
+            return false;
+        }
+        if self.sweep >= TAU {
+            return true;
+        }
+        let angle = (y * self.radii.y).atan2(x * self.radii.x);
+        (angle - self.start_angle).rem_euclid(TAU) <= self.sweep
+    }
+
+    pub fn bounds(&self) -> Rectangle {
+        let (sin, cos) = self.rotation.sin_cos();
+        let half_x = ((self.radii.x * cos).powi(2) + (self.radii.y * sin).powi(2)).sqrt();
+        let half_y = ((self.radii.x * sin).powi(2) + (self.radii.y * cos).powi(2)).sqrt();
+        // the transformed corners of the untransformed bounds enclose the
+        // transformed shape
+        let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].map(|(sx, sy)| {
+            self.transform.apply(&PointFloat::new(
+                self.center.x + sx * half_x,
+                self.center.y + sy * half_y,
+            ))
+        });
+        let min_x = corners.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+        let min_y = corners.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        let max_x = corners.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+        let max_y = corners.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        Rectangle::from_bounds(
+            Point::new(min_x.floor() as i32, min_y.floor() as i32),
+            Point::new(max_x.ceil() as i32, max_y.ceil() as i32),
+        )
+    }
+
+    /// Approximates the outline with straight segments. The outline runs
+    /// counter clockwise, unless the transform mirrors it, so a hole comes
+    /// back as a second, clockwise contour unless the wedge cuts through it.
+    pub fn flatten(&self) -> Vec<Vec<PointFloat>> {
+        let steps = ((FLATTEN_SEGMENTS as f32 * self.sweep / TAU).ceil() as usize).max(2);
+        // points along the edge at the given fraction of the radius, in
+        // the shape's own frame so that the angles are not skewed
+        let arc = |radius: f32| -> Vec<PointFloat> {
+            (0..=steps)
+                .map(|i| {
+                    let angle = self.start_angle + self.sweep * i as f32 / steps as f32;
+                    let (x, y) = (angle.cos() / self.radii.x, angle.sin() / self.radii.y);
+                    let scale = radius / (x * x + y * y).sqrt();
+                    self.from_unit(x * scale, y * scale)
+                })
+                .collect()
+        };
+
+        let full = self.sweep >= TAU;
+        let mut outer = arc(1.0);
+        if full {
+            outer.pop();
+            if self.inner <= 0.0 {
+                return vec![outer];
+            }
+            let mut inner = arc(self.inner);
+            inner.pop();
+            inner.reverse();
+            return vec![outer, inner];
+        }
+        if self.inner <= 0.0 {
+            outer.push(self.transform.apply(&self.center));
+        } else {
+            outer.extend(arc(self.inner).into_iter().rev());
+        }
+        vec![outer]
+    }
+
+    pub fn iter_points<'a>(&'a self) -> ConicIterator<'a> {
+        let bounds = self.bounds();
+        ConicIterator {
+            conic: self,
+            cur_point: bounds.origin.clone(),
+            bounds,
+        }
+    }
+}
+
+pub struct ConicIterator<'a> {
+    conic: &'a Conic,
+    bounds: Rectangle,
+    cur_point: Point,
+}
+
+impl<'a> Iterator for ConicIterator<'a> {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let ext = self.bounds.extent();
+        while self.cur_point.y < ext.y {
+            let result = self.cur_point.clone();
+            self.cur_point.x += 1;
+            if self.cur_point.x >= ext.x {
+                self.cur_point.x = self.bounds.origin.x;
+                self.cur_point.y += 1;
+            }
+            let center = PointFloat::new(result.x as f32 + 0.5, result.y as f32 + 0.5);
+            if self.conic.contains(&center) {
+                return Some(result);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,83 @@
+use super::{point::PointFloat, polygon::PolygonIterator, Point, Polygon, Rectangle};
+
+/// How much of each pixel a shape is considered to cover.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AntiAlias {
+    /// A pixel is covered completely when its center is inside, and not at
+    /// all otherwise.
+    #[default]
+    None,
+    /// Every pixel is sampled on an n by n grid, and is covered by the
+    /// fraction of the samples that are inside.
+    Supersample(u32),
+}
+
+/// Returns every pixel a polygon covers at least partly, along with the
+/// fraction of the pixel it covers. Samples follow the same top-left rule as
+/// pixel centers, so the coverage of polygons that share an edge adds up to
+/// exactly one along the edge.
+pub struct CoverageIterator<'a> {
+    polygon: &'a Polygon,
+    points: Option<PolygonIterator<'a>>,
+    samples: u32,
+    bounds: Rectangle,
+    cur_point: Point,
+}
+
+impl<'a> CoverageIterator<'a> {
+    pub(super) fn new(polygon: &'a Polygon, anti_alias: AntiAlias) -> Self {
+        let bounds = polygon.bounds();
+        let (points, samples) = match anti_alias {
+            AntiAlias::None => (Some(polygon.iter_points()), 1),
+            AntiAlias::Supersample(samples) => (None, samples.max(1)),
+        };
+        Self {
+            polygon,
+            points,
+            samples,
+            cur_point: bounds.origin.clone(),
+            bounds,
+        }
+    }
+
+    fn coverage(&self, point: &Point) -> f32 {
+        let step = 1.0 / self.samples as f32;
+        let mut inside = 0;
+        for sy in 0..self.samples {
+            for sx in 0..self.samples {
+                let sample = PointFloat::new(
+                    point.x as f32 + (sx as f32 + 0.5) * step,
+                    point.y as f32 + (sy as f32 + 0.5) * step,
+                );
+                if self.polygon.covers(&sample) {
+                    inside += 1;
+                }
+            }
+        }
+        inside as f32 / (self.samples * self.samples) as f32
+    }
+}
+
+impl<'a> Iterator for CoverageIterator<'a> {
+    type Item = (Point, f32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(points) = &mut self.points {
+            return points.next().map(|point| (point, 1.0));
+        }
+        let ext = self.bounds.extent();
+        while self.cur_point.y < ext.y {
+            let result = self.cur_point.clone();
+            self.cur_point.x += 1;
+            if self.cur_point.x >= ext.x {
+                self.cur_point.x = self.bounds.origin.x;
+                self.cur_point.y += 1;
+            }
+            let coverage = self.coverage(&result);
+            if coverage > 0.0 {
+                return Some((result, coverage));
+            }
+        }
+        None
+    }
+}
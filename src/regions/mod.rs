@@ -1,15 +1,34 @@
+mod boolean;
+mod compound_path;
+mod conic;
+mod coverage;
+mod path;
 mod point;
 mod polygon;
 mod rectangle;
+mod sdf;
+mod stroke;
 mod tessellation;
+mod tile;
+mod transform;
 mod triangle;
+mod triangulate;
 
 pub const ROOT_3: f64 = 1.732;
 
-pub use point::{Point, PointAnnotation};
+pub use boolean::BooleanOp;
+pub use compound_path::{CompoundPath, FillRule};
+pub use conic::Conic;
+pub use coverage::AntiAlias;
+pub use path::{Path, PathCommand, PathError};
+pub use point::{Point, PointAnnotation, PointFloat};
 pub use polygon::Polygon;
 pub use rectangle::{Rectangle, RectangleIterator};
-pub use triangle::{Orientation, StandardTriangle, StandardTriangleIterator, Triangle};
+pub use sdf::Sdf;
+pub use stroke::{LineCap, LineJoin, Stroke};
+pub use tile::{TileIterator, TilePoint};
+pub use transform::Transform;
+pub use triangle::{Orientation, Triangle, TriangleIterator};
 
 use crate::images::{Image, Pixel};
 
@@ -0,0 +1,531 @@
+use std::f32::consts::PI;
+use std::fmt::{self, Display};
+
+use super::{point::PointFloat, CompoundPath, FillRule, Polygon, Stroke};
+
+/// How far, in pixels, the flattened outline may stray from the curve.
+const DEFAULT_TOLERANCE: f32 = 0.25;
+const MAX_DEPTH: u32 = 16;
+
+#[derive(Clone, Debug)]
+pub enum PathCommand {
+    MoveTo(PointFloat),
+    LineTo(PointFloat),
+    QuadTo(PointFloat, PointFloat),
+    CubicTo(PointFloat, PointFloat, PointFloat),
+    /// An elliptical arc to the end point, as in SVG: the radii, the rotation
+    /// of the ellipse in degrees, and the large arc and sweep flags picking
+    /// which of the four possible arcs to draw.
+    ArcTo {
+        radii: PointFloat,
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: PointFloat,
+    },
+    Close,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathError {
+    UnexpectedCharacter(usize, char),
+    /// A command ended before all of its arguments were given.
+    MissingNumber(usize),
+    /// The path has drawing commands before its first move.
+    MissingMoveTo(usize),
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathError::UnexpectedCharacter(pos, c) => {
+                write!(f, "unexpected character '{}' at {}", c, pos)
+            }
+            PathError::MissingNumber(pos) => write!(f, "expected a number at {}", pos),
+            PathError::MissingMoveTo(pos) => write!(f, "path must start with a move at {}", pos),
+        }
+    }
+}
+
+/// Distance from `point` to the line through `start` and `end`.
+fn line_distance(point: &PointFloat, start: &PointFloat, end: &PointFloat) -> f32 {
+    let line = *end - *start;
+    let len = line.length();
+    if len < f32::EPSILON {
+        return point.distance(start);
+    }
+    line.cross(&(*point - *start)).abs() / len
+}
+
+fn flatten_quad(
+    p0: &PointFloat,
+    p1: &PointFloat,
+    p2: &PointFloat,
+    tolerance: f32,
+    depth: u32,
+    out: &mut Vec<PointFloat>,
+) {
+    if depth >= MAX_DEPTH || line_distance(p1, p0, p2) <= tolerance {
+        out.push(*p2);
+        return;
+    }
+    let (a, b) = (p0.lerp(p1, 0.5), p1.lerp(p2, 0.5));
+    let mid = a.lerp(&b, 0.5);
+    flatten_quad(p0, &a, &mid, tolerance, depth + 1, out);
+    flatten_quad(&mid, &b, p2, tolerance, depth + 1, out);
+}
+
+fn flatten_cubic(p: [&PointFloat; 4], tolerance: f32, depth: u32, out: &mut Vec<PointFloat>) {
+    let [p0, p1, p2, p3] = p;
+    let flat = line_distance(p1, p0, p3).max(line_distance(p2, p0, p3)) <= tolerance;
+    if depth >= MAX_DEPTH || flat {
+        out.push(*p3);
+        return;
+    }
+    // de casteljau split at the middle
+    let (a, b, c) = (p0.lerp(p1, 0.5), p1.lerp(p2, 0.5), p2.lerp(p3, 0.5));
+    let (d, e) = (a.lerp(&b, 0.5), b.lerp(&c, 0.5));
+    let mid = d.lerp(&e, 0.5);
+    flatten_cubic([p0, &a, &d, &mid], tolerance, depth + 1, out);
+    flatten_cubic([&mid, &e, &c, p3], tolerance, depth + 1, out);
+}
+
+/// Converts the SVG end point parameterization of an arc to its center
+/// parameterization and samples it.
+fn flatten_arc(
+    from: &PointFloat,
+    radii: &PointFloat,
+    rotation: f32,
+    (large_arc, sweep): (bool, bool),
+    to: &PointFloat,
+    tolerance: f32,
+    out: &mut Vec<PointFloat>,
+) {
+    let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+    if rx < f32::EPSILON || ry < f32::EPSILON || from.distance(to) < f32::EPSILON {
+        out.push(*to);
+        return;
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let (hx, hy) = ((from.x - to.x) / 2.0, (from.y - to.y) / 2.0);
+    let x1 = cos * hx + sin * hy;
+    let y1 = -sin * hx + cos * hy;
+
+    // scale up radii that are too small to reach the end point
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let mut factor = (num / den).max(0.0).sqrt();
+    if large_arc == sweep {
+        factor = -factor;
+    }
+    let cx1 = factor * rx * y1 / ry;
+    let cy1 = -factor * ry * x1 / rx;
+    let cx = cos * cx1 - sin * cy1 + (from.x + to.x) / 2.0;
+    let cy = sin * cx1 + cos * cy1 + (from.y + to.y) / 2.0;
+
+    let angle = |ux: f32, uy: f32| uy.atan2(ux);
+    let start = angle((x1 - cx1) / rx, (y1 - cy1) / ry);
+    let mut delta = angle((-x1 - cx1) / rx, (-y1 - cy1) / ry) - start;
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    } else if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+
+    let radius = rx.max(ry);
+    let step = 2.0 * (1.0 - tolerance / radius).clamp(-1.0, 1.0).acos();
+    let segments = ((delta.abs() / step.max(1e-3)).ceil() as i32).clamp(1, 1024);
+    for i in 1..segments {
+        let (s, c) = (start + delta * i as f32 / segments as f32).sin_cos();
+        let (ex, ey) = (rx * c, ry * s);
+        out.push(PointFloat::new(
+            cx + cos * ex - sin * ey,
+            cy + sin * ex + cos * ey,
+        ));
+    }
+    out.push(*to);
+}
+
+/// A sequence of straight and curved outlines, made of one or more sub paths
+/// that each begin with a move. Curves are flattened into line segments when
+/// the path is filled or stroked.
+#[derive(Clone, Debug)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+    tolerance: f32,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self {
+            commands: vec![],
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    pub fn move_to(mut self, to: PointFloat) -> Self {
+        self.commands.push(PathCommand::MoveTo(to));
+        self
+    }
+
+    pub fn line_to(mut self, to: PointFloat) -> Self {
+        self.commands.push(PathCommand::LineTo(to));
+        self
+    }
+
+    pub fn quad_to(mut self, control: PointFloat, to: PointFloat) -> Self {
+        self.commands.push(PathCommand::QuadTo(control, to));
+        self
+    }
+
+    pub fn cubic_to(
+        mut self,
+        control_1: PointFloat,
+        control_2: PointFloat,
+        to: PointFloat,
+    ) -> Self {
+        self.commands
+            .push(PathCommand::CubicTo(control_1, control_2, to));
+        self
+    }
+
+    pub fn arc_to(
+        mut self,
+        radii: PointFloat,
+        rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        to: PointFloat,
+    ) -> Self {
+        self.commands.push(PathCommand::ArcTo {
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+            to,
+        });
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    /// Sets how far, in pixels, flattened curves may stray from the real
+    /// curve.
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance.max(0.01);
+        self
+    }
+
+    pub fn commands(&self) -> &Vec<PathCommand> {
+        &self.commands
+    }
+
+    /// Returns every sub path as a list of points and whether it was closed.
+    pub fn flatten(&self) -> Vec<(Vec<PointFloat>, bool)> {
+        let mut result = vec![];
+        let mut current: Vec<PointFloat> = vec![];
+        let mut start = PointFloat::new(0.0, 0.0);
+        let mut last = PointFloat::new(0.0, 0.0);
+        for command in self.commands.iter() {
+            match command {
+                PathCommand::MoveTo(to) => {
+                    if current.len() > 1 {
+                        result.push((std::mem::take(&mut current), false));
+                    }
+                    current = vec![*to];
+                    start = *to;
+                }
+                PathCommand::LineTo(to) => current.push(*to),
+                PathCommand::QuadTo(control, to) => {
+                    flatten_quad(&last, control, to, self.tolerance, 0, &mut current)
+                }
+                PathCommand::CubicTo(c1, c2, to) => {
+                    flatten_cubic([&last, c1, c2, to], self.tolerance, 0, &mut current)
+                }
+                PathCommand::ArcTo {
+                    radii,
+                    rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => flatten_arc(
+                    &last,
+                    radii,
+                    *rotation,
+                    (*large_arc, *sweep),
+                    to,
+                    self.tolerance,
+                    &mut current,
+                ),
+                PathCommand::Close => {
+                    if current.len() > 1 {
+                        result.push((std::mem::take(&mut current), true));
+                    }
+                    // drawing after a close continues from the start of the
+                    // closed sub path
+                    current = vec![start];
+                }
+            }
+            last = current.last().cloned().unwrap_or_else(|| start);
+        }
+        if current.len() > 1 {
+            result.push((current, false));
+        }
+        result
+    }
+
+    /// Fills the path, closing any open sub paths.
+    pub fn fill(&self, fill_rule: FillRule) -> Polygon {
+        let contours = self
+            .flatten()
+            .into_iter()
+            .map(|(points, _)| points)
+            .collect();
+        Polygon::from_path(CompoundPath::new(contours, fill_rule))
+    }
+
+    pub fn stroke(&self, stroke: &Stroke) -> Polygon {
+        let contours = self
+            .flatten()
+            .iter()
+            .flat_map(|(points, closed)| stroke.polyline(points, *closed).contours())
+            .collect();
+        Polygon::from_path(CompoundPath::new(contours, FillRule::NonZero))
+    }
+
+    /// Parses the `d` attribute of an SVG path element.
+    pub fn parse(d: &str) -> Result<Path, PathError> {
+        PathParser::new(d).parse()
+    }
+}
+
+impl Default for Path {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct PathParser {
+    chars: Vec<char>,
+    pos: usize,
+    path: Path,
+    current: PointFloat,
+    start: PointFloat,
+    /// The last control point, reflected by the smooth curve commands.
+    control: Option<(char, PointFloat)>,
+}
+
+impl PathParser {
+    fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+            path: Path::new(),
+            current: PointFloat::new(0.0, 0.0),
+            start: PointFloat::new(0.0, 0.0),
+            control: None,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self.pos < self.chars.len()
+            && (self.chars[self.pos].is_whitespace() || self.chars[self.pos] == ',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+        matches!(self.chars.get(self.pos), Some(c) if c.is_ascii_digit() || "+-.".contains(*c))
+    }
+
+    fn number(&mut self) -> Result<f32, PathError> {
+        self.skip_separators();
+        let begin = self.pos;
+        let mut end = self.pos;
+        let peek = |i: usize| self.chars.get(i).copied();
+        if matches!(peek(end), Some('+' | '-')) {
+            end += 1;
+        }
+        let mut seen_dot = false;
+        while let Some(c) = peek(end) {
+            if c.is_ascii_digit() {
+                end += 1;
+            } else if c == '.' && !seen_dot {
+                seen_dot = true;
+                end += 1;
+            } else {
+                break;
+            }
+        }
+        if matches!(peek(end), Some('e' | 'E')) {
+            let mut exp = end + 1;
+            if matches!(peek(exp), Some('+' | '-')) {
+                exp += 1;
+            }
+            if matches!(peek(exp), Some(c) if c.is_ascii_digit()) {
+                end = exp;
+                while matches!(peek(end), Some(c) if c.is_ascii_digit()) {
+                    end += 1;
+                }
+            }
+        }
+        let text: String = self.chars[begin..end].iter().collect();
+        match text.parse::<f32>() {
+            Ok(value) => {
+                self.pos = end;
+                Ok(value)
+            }
+            Err(_) => Err(PathError::MissingNumber(begin)),
+        }
+    }
+
+    /// Arc flags are single digits and may be written without separators.
+    fn flag(&mut self) -> Result<bool, PathError> {
+        self.skip_separators();
+        match self.chars.get(self.pos) {
+            Some('0') => {
+                self.pos += 1;
+                Ok(false)
+            }
+            Some('1') => {
+                self.pos += 1;
+                Ok(true)
+            }
+            _ => Err(PathError::MissingNumber(self.pos)),
+        }
+    }
+
+    fn point(&mut self, relative: bool) -> Result<PointFloat, PathError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok(match relative {
+            true => PointFloat::new(self.current.x + x, self.current.y + y),
+            false => PointFloat::new(x, y),
+        })
+    }
+
+    fn reflected_control(&self, kinds: &str) -> PointFloat {
+        match &self.control {
+            Some((kind, control)) if kinds.contains(*kind) => PointFloat::new(
+                2.0 * self.current.x - control.x,
+                2.0 * self.current.y - control.y,
+            ),
+            _ => self.current,
+        }
+    }
+
+    fn push(&mut self, command: PathCommand) {
+        self.path.commands.push(command);
+    }
+
+    fn parse(mut self) -> Result<Path, PathError> {
+        let mut command: Option<char> = None;
+        loop {
+            self.skip_separators();
+            let Some(&c) = self.chars.get(self.pos) else {
+                break;
+            };
+            if c.is_ascii_alphabetic() {
+                if !"MmLlHhVvCcSsQqTtAaZz".contains(c) {
+                    return Err(PathError::UnexpectedCharacter(self.pos, c));
+                }
+                if command.is_none() && c != 'M' && c != 'm' {
+                    return Err(PathError::MissingMoveTo(self.pos));
+                }
+                self.pos += 1;
+                command = Some(c);
+            } else if !self.at_number() {
+                return Err(PathError::UnexpectedCharacter(self.pos, c));
+            } else if matches!(command, None | Some('Z' | 'z')) {
+                // numbers after a close need a new command
+                return Err(PathError::UnexpectedCharacter(self.pos, c));
+            }
+            let cmd = command.unwrap();
+            let relative = cmd.is_ascii_lowercase();
+            match cmd.to_ascii_uppercase() {
+                'M' => {
+                    let to = self.point(relative)?;
+                    self.start = to;
+                    self.current = to;
+                    self.push(PathCommand::MoveTo(to));
+                    self.control = None;
+                    // further coordinate pairs are implicit line commands
+                    command = Some(if relative { 'l' } else { 'L' });
+                }
+                'L' => {
+                    let to = self.point(relative)?;
+                    self.line(to);
+                }
+                'H' => {
+                    let x = self.number()?;
+                    let x = if relative { self.current.x + x } else { x };
+                    self.line(PointFloat::new(x, self.current.y));
+                }
+                'V' => {
+                    let y = self.number()?;
+                    let y = if relative { self.current.y + y } else { y };
+                    self.line(PointFloat::new(self.current.x, y));
+                }
+                'C' | 'S' => {
+                    let c1 = match cmd.to_ascii_uppercase() {
+                        'C' => self.point(relative)?,
+                        _ => self.reflected_control("CS"),
+                    };
+                    let c2 = self.point(relative)?;
+                    let to = self.point(relative)?;
+                    self.control = Some(('C', c2));
+                    self.current = to;
+                    self.push(PathCommand::CubicTo(c1, c2, to));
+                }
+                'Q' | 'T' => {
+                    let control = match cmd.to_ascii_uppercase() {
+                        'Q' => self.point(relative)?,
+                        _ => self.reflected_control("QT"),
+                    };
+                    let to = self.point(relative)?;
+                    self.control = Some(('Q', control));
+                    self.current = to;
+                    self.push(PathCommand::QuadTo(control, to));
+                }
+                'A' => {
+                    let radii = PointFloat::new(self.number()?, self.number()?);
+                    let rotation = self.number()?;
+                    let large_arc = self.flag()?;
+                    let sweep = self.flag()?;
+                    let to = self.point(relative)?;
+                    self.current = to;
+                    self.control = None;
+                    self.push(PathCommand::ArcTo {
+                        radii,
+                        rotation,
+                        large_arc,
+                        sweep,
+                        to,
+                    });
+                }
+                _ => {
+                    self.current = self.start;
+                    self.control = None;
+                    self.push(PathCommand::Close);
+                }
+            }
+        }
+        Ok(self.path)
+    }
+
+    fn line(&mut self, to: PointFloat) {
+        self.current = to;
+        self.control = None;
+        self.push(PathCommand::LineTo(to));
+    }
+}
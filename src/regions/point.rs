@@ -1,12 +1,15 @@
 use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
 
 const ORIGIN: Point = Point::new(0, 0);
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum PointAnnotation {
     Regular,
-    FullEdge,
     StandardEdge,
+    /// A shape covers only part of the pixel, so its color is composited
+    /// over the image instead of replacing it.
+    Partial,
 }
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -34,6 +37,14 @@ impl Point {
         self
     }
 
+    /// Marks the point as partly covered when `coverage` is below 1.
+    pub fn with_coverage(self, coverage: f32) -> Self {
+        match coverage < 1.0 {
+            true => self.annotate(PointAnnotation::Partial),
+            false => self,
+        }
+    }
+
     pub fn from_linear(value: i32, max_x: i32) -> Self {
         Self {
             x: value % max_x,
@@ -69,7 +80,10 @@ impl Debug for Point {
     }
 }
 
-#[derive(Clone)]
+/// A sub pixel position, which is also used as a 2D vector. Pixel `(x, y)`
+/// covers the square from `(x, y)` to `(x + 1, y + 1)`, so its center is at
+/// `(x + 0.5, y + 0.5)`.
+#[derive(Clone, Copy, Default, PartialEq)]
 pub struct PointFloat {
     pub x: f32,
     pub y: f32,
@@ -99,6 +113,113 @@ impl PointFloat {
     pub fn inv_slope(&self, other: &PointFloat) -> f32 {
         (other.x - self.x) / (other.y - self.y)
     }
+
+    /// The center of the given pixel.
+    pub fn pixel_center(point: &Point) -> Self {
+        Self::new(point.x as f32 + 0.5, point.y as f32 + 0.5)
+    }
+
+    pub fn dot(&self, other: &PointFloat) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the 3D cross product, positive when `other` is
+    /// counter clockwise from `self`.
+    pub fn cross(&self, other: &PointFloat) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn length(&self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /// Returns the vector scaled to length 1, or the zero vector unchanged.
+    pub fn normalize(&self) -> Self {
+        let len = self.length();
+        if len == 0.0 {
+            return *self;
+        }
+        *self / len
+    }
+
+    /// The vector rotated a quarter turn counter clockwise.
+    pub fn perpendicular(&self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    pub fn lerp(&self, other: &PointFloat, t: f32) -> Self {
+        *self + (*other - *self) * t
+    }
+
+    /// The distance to the closest point of the line segment from `from` to
+    /// `to`.
+    pub fn distance_to_segment(&self, from: &PointFloat, to: &PointFloat) -> f32 {
+        let edge = *to - *from;
+        let length_sq = edge.dot(&edge);
+        if length_sq == 0.0 {
+            return self.distance(from);
+        }
+        let t = ((*self - *from).dot(&edge) / length_sq).clamp(0.0, 1.0);
+        self.distance(&from.lerp(to, t))
+    }
+}
+
+impl From<Point> for PointFloat {
+    fn from(point: Point) -> Self {
+        point.to_float()
+    }
+}
+
+impl Add for PointFloat {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for PointFloat {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f32> for PointFloat {
+    type Output = Self;
+
+    fn mul(self, factor: f32) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Div<f32> for PointFloat {
+    type Output = Self;
+
+    fn div(self, divisor: f32) -> Self {
+        Self::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl Neg for PointFloat {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for PointFloat {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for PointFloat {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
 }
 
 impl Debug for PointFloat {
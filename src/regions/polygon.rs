@@ -1,21 +1,34 @@
 use super::{
-    point::Point, Rectangle, RectangleIterator, StandardTriangle, StandardTriangleIterator,
-    Triangle,
+    boolean::{self, BooleanOp},
+    compound_path::{counter_clockwise, CompoundPath, CompoundPathIterator, FillRule},
+    conic::{Conic, ConicIterator},
+    coverage::{AntiAlias, CoverageIterator},
+    point::{Point, PointFloat},
+    tile::TileIterator,
+    triangulate, Rectangle, RectangleIterator, Transform, Triangle, TriangleIterator,
 };
 
+#[derive(Clone)]
 pub struct Polygon {
-    triangles: Vec<StandardTriangle>,
+    triangles: Vec<Triangle>,
     rectangles: Vec<Rectangle>,
+    paths: Vec<CompoundPath>,
+    conics: Vec<Conic>,
     bounding_box: Rectangle,
 }
 
 impl Polygon {
-    pub fn from_shapes(triangles: Vec<StandardTriangle>, rectangles: Vec<Rectangle>) -> Self {
-        println!(
-            "Generating polygon with {} triangles and {} rectangles",
-            triangles.len(),
-            rectangles.len()
-        );
+    pub fn from_shapes(triangles: Vec<Triangle>, rectangles: Vec<Rectangle>) -> Self {
+        Self::from_parts(triangles, rectangles, vec![], vec![])
+    }
+
+    pub fn from_parts(
+        triangles: Vec<Triangle>,
+        rectangles: Vec<Rectangle>,
+        paths: Vec<CompoundPath>,
+        conics: Vec<Conic>,
+    ) -> Self {
+        let paths: Vec<CompoundPath> = paths.into_iter().filter(|p| !p.is_empty()).collect();
         let origin = Point::new(i32::MAX, i32::MAX);
         let extent = Point::new(0, 0);
         let mut bounding_box = Rectangle::from_bounds(origin, extent);
@@ -25,25 +38,82 @@ impl Polygon {
         for rect in rectangles.iter() {
             bounding_box.union(&rect.bounds());
         }
-        println!("Found boundinb box: {:?}", bounding_box);
+        for path in paths.iter() {
+            bounding_box.union(&path.bounds());
+        }
+        for conic in conics.iter() {
+            bounding_box.union(&conic.bounds());
+        }
         Self {
             triangles,
             rectangles,
+            paths,
+            conics,
             bounding_box,
         }
     }
 
-    pub fn triangle(p1: Point, p2: Point, p3: Point) -> Self {
-        let tri = Triangle::new(p1, p2, p3);
-        let (t1, maybe_t2) = tri.to_triangles();
-        let mut triangles = vec![t1];
-        if let Some(t2) = maybe_t2 {
-            triangles.push(t2);
+    pub fn from_path(path: CompoundPath) -> Self {
+        Self::from_parts(vec![], vec![], vec![path], vec![])
+    }
+
+    pub fn from_conic(conic: Conic) -> Self {
+        Self::from_parts(vec![], vec![], vec![], vec![conic])
+    }
+
+    /// Returns a copy where every curved part is replaced by its straight
+    /// edged approximation, so that `contains` agrees with `contours`.
+    pub fn flattened(&self) -> Polygon {
+        let mut paths = self.paths.clone();
+        for conic in self.conics.iter() {
+            paths.push(CompoundPath::new(conic.flatten(), FillRule::NonZero));
         }
+        Self::from_parts(
+            self.triangles.clone(),
+            self.rectangles.clone(),
+            paths,
+            vec![],
+        )
+    }
+
+    /// Builds a polygon from an outline with holes cut out of it. Holes must
+    /// lie inside the outline and must not overlap each other.
+    pub fn with_holes(outline: &[Point], holes: &[Vec<Point>]) -> Self {
+        let mut contours = vec![outline.to_vec()];
+        contours.extend(holes.iter().cloned());
+        Self::compound(&contours, FillRule::EvenOdd)
+    }
+
+    /// Builds a polygon from any number of outlines, which may overlap, nest
+    /// or be disjoint. The fill rule decides which of the enclosed areas are
+    /// filled.
+    pub fn compound(contours: &[Vec<Point>], fill_rule: FillRule) -> Self {
+        Self::from_path(CompoundPath::from_points(contours, fill_rule))
+    }
+
+    pub fn triangle(p1: Point, p2: Point, p3: Point) -> Self {
+        Self::from_shapes(vec![Triangle::new(p1, p2, p3)], vec![])
+    }
+
+    /// A triangle at a sub pixel position.
+    pub fn float_triangle(p1: PointFloat, p2: PointFloat, p3: PointFloat) -> Self {
+        Self::from_shapes(vec![Triangle::new_float(p1, p2, p3)], vec![])
+    }
 
+    pub fn from_triangles(triangles: Vec<Triangle>) -> Self {
         Self::from_shapes(triangles, vec![])
     }
 
+    /// Builds a polygon from any simple outline, convex or concave, in either
+    /// winding order.
+    pub fn from_vertices(vertices: &[Point]) -> Self {
+        let triangles = triangulate::ear_clip(vertices)
+            .into_iter()
+            .map(|[p1, p2, p3]| Triangle::new(p1, p2, p3))
+            .collect();
+        Self::from_triangles(triangles)
+    }
+
     pub fn iter_points(&self) -> PolygonIterator {
         let cur_rect_iterator;
         if self.rectangles.len() > 0 {
@@ -55,26 +125,182 @@ impl Polygon {
             true => Some(self.triangles[0].iter_points()),
             false => None,
         };
+        let cur_path_iterator = self.paths.first().map(|path| path.iter_points());
+        let cur_conic_iterator = self.conics.first().map(|conic| conic.iter_points());
         PolygonIterator {
             polygon: self,
             cur_rect_index: 0,
             cur_tri_index: 0,
+            cur_path_index: 0,
+            cur_conic_index: 0,
             cur_rect_iterator,
             cur_tri_iterator,
+            cur_path_iterator,
+            cur_conic_iterator,
         }
     }
 
+    /// Returns every pixel the polygon covers with the fraction of it that is
+    /// covered. Without anti aliasing these are the pixels of `iter_points`,
+    /// all covered completely.
+    pub fn iter_coverage(&self, anti_alias: AntiAlias) -> CoverageIterator<'_> {
+        CoverageIterator::new(self, anti_alias)
+    }
+
+    /// Returns every pixel the polygon covers like `iter_coverage`, along
+    /// with its distance to the edge and its position within the bounds.
+    pub fn iter_tile(&self, anti_alias: AntiAlias) -> TileIterator<'_> {
+        TileIterator::new(std::slice::from_ref(self), anti_alias)
+    }
+
     pub fn bounds(&self) -> Rectangle {
         self.bounding_box.clone()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+            && self.rectangles.is_empty()
+            && self.paths.is_empty()
+            && self.conics.is_empty()
+    }
+
+    pub fn contains(&self, point: &PointFloat) -> bool {
+        self.triangles.iter().any(|tri| tri.contains(point))
+            || self.rectangles.iter().any(|rect| {
+                let ext = rect.extent();
+                point.x >= rect.origin.x as f32
+                    && point.x < ext.x as f32
+                    && point.y >= rect.origin.y as f32
+                    && point.y < ext.y as f32
+            })
+            || self.paths.iter().any(|path| path.contains(point))
+            || self.conics.iter().any(|conic| conic.contains(point))
+    }
+
+    /// Whether the point is inside under the same rules that decide which
+    /// pixel centers are inside, so that a point on an edge shared with
+    /// another polygon belongs to exactly one of them.
+    pub fn covers(&self, point: &PointFloat) -> bool {
+        self.triangles.iter().any(|tri| tri.covers(point))
+            || self.rectangles.iter().any(|rect| {
+                let ext = rect.extent();
+                point.x >= rect.origin.x as f32
+                    && point.x < ext.x as f32
+                    && point.y >= rect.origin.y as f32
+                    && point.y < ext.y as f32
+            })
+            || self.paths.iter().any(|path| path.contains(point))
+            || self.conics.iter().any(|conic| conic.contains(point))
+    }
+
+    /// Returns the outline of every part of the polygon. Parts may overlap or
+    /// share edges, so these are not necessarily the edges of the polygon.
+    pub fn contours(&self) -> Vec<Vec<PointFloat>> {
+        let mut result: Vec<Vec<PointFloat>> = vec![];
+        for tri in self.triangles.iter() {
+            result.push(tri.vertices().to_vec());
+        }
+        for rect in self.rectangles.iter() {
+            let ext = rect.extent();
+            result.push(vec![
+                rect.origin.to_float(),
+                PointFloat::new(ext.x as f32, rect.origin.y as f32),
+                ext.to_float(),
+                PointFloat::new(rect.origin.x as f32, ext.y as f32),
+            ]);
+        }
+        for path in self.paths.iter() {
+            result.extend(path.contours().iter().cloned());
+        }
+        for conic in self.conics.iter() {
+            result.extend(conic.flatten());
+        }
+        result
+    }
+
+    /// Applies the transform to every part of the polygon. Triangles and
+    /// rectangles are no longer axis aligned afterwards, so they are turned
+    /// into a single outline path.
+    pub fn transform(&self, transform: &Transform) -> Polygon {
+        let straight: Vec<Vec<PointFloat>> =
+            Self::from_shapes(self.triangles.clone(), self.rectangles.clone())
+                .contours()
+                .iter()
+                .map(|contour| {
+                    counter_clockwise(contour.iter().map(|p| transform.apply(p)).collect())
+                })
+                .collect();
+        let mut paths = vec![CompoundPath::new(straight, FillRule::NonZero)];
+        paths.extend(self.paths.iter().map(|path| path.transform(transform)));
+        let conics = self
+            .conics
+            .iter()
+            .map(|conic| conic.transform(transform))
+            .collect();
+        Self::from_parts(vec![], vec![], paths, conics)
+    }
+
+    /// The center of mass of the area the polygon covers, or the center of
+    /// its bounds when it covers none.
+    pub fn centroid(&self) -> PointFloat {
+        let mut area = 0.0;
+        let mut sum = PointFloat::default();
+        for contour in self.boundary() {
+            for (i, a) in contour.iter().enumerate() {
+                let b = &contour[(i + 1) % contour.len()];
+                let cross = a.cross(b);
+                area += cross;
+                sum += (*a + *b) * cross;
+            }
+        }
+        if area.abs() < f32::EPSILON {
+            return self.bounds().center().to_float();
+        }
+        sum / (3.0 * area)
+    }
+
+    /// Returns the edges of the polygon itself, with the edges shared between
+    /// its parts removed.
+    pub fn boundary(&self) -> Vec<Vec<PointFloat>> {
+        let empty = Polygon::from_parts(vec![], vec![], vec![], vec![]);
+        self.union(&empty).contours()
+    }
+
+    pub fn combine(&self, other: &Polygon, op: BooleanOp) -> Polygon {
+        boolean::combine(self, other, op)
+    }
+
+    pub fn union(&self, other: &Polygon) -> Polygon {
+        self.combine(other, BooleanOp::Union)
+    }
+
+    pub fn intersection(&self, other: &Polygon) -> Polygon {
+        self.combine(other, BooleanOp::Intersection)
+    }
+
+    pub fn difference(&self, other: &Polygon) -> Polygon {
+        self.combine(other, BooleanOp::Difference)
+    }
+
+    pub fn xor(&self, other: &Polygon) -> Polygon {
+        self.combine(other, BooleanOp::Xor)
+    }
+
+    pub fn clip(&self, bounds: &Rectangle) -> Polygon {
+        self.intersection(&Polygon::from(bounds.clone()))
+    }
 }
 
 pub struct PolygonIterator<'a> {
     polygon: &'a Polygon,
     cur_rect_index: usize,
     cur_tri_index: usize,
+    cur_path_index: usize,
+    cur_conic_index: usize,
     cur_rect_iterator: Option<RectangleIterator<'a>>,
-    cur_tri_iterator: Option<StandardTriangleIterator<'a>>,
+    cur_tri_iterator: Option<TriangleIterator<'a>>,
+    cur_path_iterator: Option<CompoundPathIterator<'a>>,
+    cur_conic_iterator: Option<ConicIterator<'a>>,
 }
 
 impl<'a> Iterator for PolygonIterator<'a> {
@@ -121,6 +347,29 @@ impl<'a> Iterator for PolygonIterator<'a> {
                 }
             }
         }
+        // then the paths
+        while let Some(path_iter) = &mut self.cur_path_iterator {
+            if let Some(result) = path_iter.next() {
+                return Some(result);
+            }
+            self.cur_path_index += 1;
+            self.cur_path_iterator = self
+                .polygon
+                .paths
+                .get(self.cur_path_index)
+                .map(|path| path.iter_points());
+        }
+        while let Some(conic_iter) = &mut self.cur_conic_iterator {
+            if let Some(result) = conic_iter.next() {
+                return Some(result);
+            }
+            self.cur_conic_index += 1;
+            self.cur_conic_iterator = self
+                .polygon
+                .conics
+                .get(self.cur_conic_index)
+                .map(|conic| conic.iter_points());
+        }
 
         None
     }
@@ -128,7 +377,7 @@ impl<'a> Iterator for PolygonIterator<'a> {
 
 impl From<Triangle> for Polygon {
     fn from(triangle: Triangle) -> Self {
-        Self::from_shapes(triangle.to_triangle_vec(), vec![])
+        Self::from_shapes(vec![triangle], vec![])
     }
 }
 
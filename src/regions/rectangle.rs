@@ -42,6 +42,14 @@ impl Rectangle {
         self
     }
 
+    /// The rectangle with its origin and size multiplied by `factor`.
+    pub fn scale(&self, factor: i32) -> Self {
+        Self {
+            origin: Point::new(self.origin.x * factor, self.origin.y * factor),
+            size: Point::new(self.size.x * factor, self.size.y * factor),
+        }
+    }
+
     pub fn extent(&self) -> Point {
         Point::new(self.origin.x + self.size.x, self.origin.y + self.size.y)
     }
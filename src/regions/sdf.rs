@@ -0,0 +1,174 @@
+use std::f32::consts::PI;
+use std::rc::Rc;
+
+use super::{point::PointFloat, Point};
+use crate::curves::CurveFn;
+
+/// A shape given by its signed distance function: the distance from any
+/// point to the nearest edge of the shape, negative inside of it.
+///
+/// Shapes are built around the origin and moved into place with `translate`
+/// and `rotate`, which keep the distances exact. Combinators like
+/// `smooth_union` and `round` only give an estimate of the distance, which is
+/// still exact near the edges, where it matters for anti aliasing.
+#[derive(Clone)]
+pub struct Sdf {
+    distance: Rc<dyn Fn(&PointFloat) -> f32>,
+}
+
+/// The signed distance to a closed outline, with the sign decided by the
+/// even-odd rule.
+fn polygon_distance(vertices: &[PointFloat], point: &PointFloat) -> f32 {
+    let mut distance_sq = f32::MAX;
+    let mut sign = 1.0;
+    for i in 0..vertices.len() {
+        let (a, b) = (vertices[i], vertices[(i + 1) % vertices.len()]);
+        let edge = b - a;
+        let to_point = *point - a;
+        let t = (to_point.dot(&edge) / edge.dot(&edge)).clamp(0.0, 1.0);
+        let closest = to_point - edge * t;
+        distance_sq = distance_sq.min(closest.dot(&closest));
+        // the edge crosses the horizontal line through the point to its
+        // right
+        let crosses = (a.y <= point.y) != (b.y <= point.y);
+        if crosses && (edge.cross(&to_point) > 0.0) == (edge.y > 0.0) {
+            sign = -sign;
+        }
+    }
+    sign * distance_sq.sqrt()
+}
+
+impl Sdf {
+    pub fn new(distance: impl Fn(&PointFloat) -> f32 + 'static) -> Self {
+        Self {
+            distance: Rc::new(distance),
+        }
+    }
+
+    pub fn circle(radius: f32) -> Self {
+        Self::new(move |p| p.length() - radius)
+    }
+
+    /// A rectangle centered on the origin.
+    pub fn rect(width: f32, height: f32) -> Self {
+        let half = PointFloat::new(width / 2.0, height / 2.0);
+        Self::new(move |p| {
+            let outside = PointFloat::new(p.x.abs() - half.x, p.y.abs() - half.y);
+            let corner = PointFloat::new(outside.x.max(0.0), outside.y.max(0.0));
+            corner.length() + outside.x.max(outside.y).min(0.0)
+        })
+    }
+
+    pub fn rounded_rect(width: f32, height: f32, radius: f32) -> Self {
+        let radius = radius.min(width / 2.0).min(height / 2.0);
+        Self::rect(width - 2.0 * radius, height - 2.0 * radius).round(radius)
+    }
+
+    /// Any closed outline, which may be concave.
+    pub fn polygon(vertices: Vec<PointFloat>) -> Self {
+        Self::new(move |p| polygon_distance(&vertices, p))
+    }
+
+    /// A hexagon with its corners at the given distance from the center and
+    /// a corner at the top, like the hexagons of `Hexagon`.
+    pub fn hexagon(radius: f32) -> Self {
+        Self::polygon(
+            (0..6)
+                .map(|i| {
+                    let angle = PI / 2.0 + i as f32 * PI / 3.0;
+                    PointFloat::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect(),
+        )
+    }
+
+    /// A star with its first point at the top.
+    pub fn star(outer: f32, inner: f32, points: u32) -> Self {
+        Self::polygon(
+            (0..points * 2)
+                .map(|i| {
+                    let angle = PI / 2.0 + i as f32 * PI / points as f32;
+                    let radius = if i % 2 == 0 { outer } else { inner };
+                    PointFloat::new(angle.cos(), angle.sin()) * radius
+                })
+                .collect(),
+        )
+    }
+
+    /// A line with round ends.
+    pub fn segment(from: PointFloat, to: PointFloat, width: f32) -> Self {
+        Self::new(move |p| p.distance_to_segment(&from, &to) - width / 2.0)
+    }
+
+    pub fn distance(&self, point: &PointFloat) -> f32 {
+        (self.distance)(point)
+    }
+
+    pub fn translate(self, x: f32, y: f32) -> Self {
+        Self::new(move |p| self.distance(&PointFloat::new(p.x - x, p.y - y)))
+    }
+
+    /// Rotates counter clockwise around the origin, in degrees.
+    pub fn rotate(self, degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self::new(move |p| {
+            self.distance(&PointFloat::new(
+                p.x * cos + p.y * sin,
+                -p.x * sin + p.y * cos,
+            ))
+        })
+    }
+
+    pub fn union(self, other: Sdf) -> Self {
+        Self::new(move |p| self.distance(p).min(other.distance(p)))
+    }
+
+    /// A union that melts the shapes into each other where they are closer
+    /// than `smoothness`.
+    pub fn smooth_union(self, other: Sdf, smoothness: f32) -> Self {
+        Self::new(move |p| {
+            let (a, b) = (self.distance(p), other.distance(p));
+            let h = (0.5 + 0.5 * (b - a) / smoothness).clamp(0.0, 1.0);
+            b + (a - b) * h - smoothness * h * (1.0 - h)
+        })
+    }
+
+    pub fn subtract(self, other: Sdf) -> Self {
+        Self::new(move |p| self.distance(p).max(-other.distance(p)))
+    }
+
+    pub fn intersect(self, other: Sdf) -> Self {
+        Self::new(move |p| self.distance(p).max(other.distance(p)))
+    }
+
+    /// Grows the shape by `radius`, rounding its corners.
+    pub fn round(self, radius: f32) -> Self {
+        Self::new(move |p| self.distance(p) - radius)
+    }
+
+    /// Replaces the shape by a shell of the given width around its edge.
+    pub fn onion(self, width: f32) -> Self {
+        Self::new(move |p| self.distance(p).abs() - width / 2.0)
+    }
+
+    /// Repeats the part of the shape around the origin on a grid with the
+    /// given spacing, forever.
+    pub fn repeat(self, spacing: PointFloat) -> Self {
+        Self::new(move |p| {
+            self.distance(&PointFloat::new(
+                p.x - spacing.x * (p.x / spacing.x).round(),
+                p.y - spacing.y * (p.y / spacing.y).round(),
+            ))
+        })
+    }
+
+    /// Exposes the distance to the edge at every pixel as a curve, mapped
+    /// through `map`, for colors that follow the shape like glows, outlines
+    /// and bevels.
+    pub fn curve(&self, map: impl Fn(f32) -> f32 + 'static) -> CurveFn {
+        let sdf = self.clone();
+        Box::new(move |point: &Point, _bounds: &Point| {
+            map(sdf.distance(&PointFloat::pixel_center(point)))
+        })
+    }
+}
@@ -0,0 +1,268 @@
+use super::{
+    compound_path::counter_clockwise, point::PointFloat, CompoundPath, Conic, FillRule, Polygon,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point.
+    Butt,
+    /// The stroke ends in a half circle around the end point.
+    Round,
+    /// The stroke extends past the end point by half its width.
+    Square,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineJoin {
+    /// The outer edges are extended until they meet, falling back to a bevel
+    /// when the point would be further than the miter limit.
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// Describes how lines are drawn. All the pieces of a stroke are counter
+/// clockwise outlines filled with the non-zero rule, so where they overlap
+/// they simply merge.
+#[derive(Clone, Debug)]
+pub struct Stroke {
+    width: f32,
+    cap: LineCap,
+    join: LineJoin,
+    miter_limit: f32,
+    dash: Vec<f32>,
+    dash_offset: f32,
+}
+
+impl Stroke {
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+            dash: vec![],
+            dash_offset: 0.0,
+        }
+    }
+
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// The longest a miter may be, as a multiple of the stroke width.
+    pub fn miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    /// Alternating lengths of dashes and gaps, starting `offset` into the
+    /// pattern.
+    pub fn dashed(mut self, pattern: Vec<f32>, offset: f32) -> Self {
+        self.dash = pattern;
+        self.dash_offset = offset;
+        self
+    }
+
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn line(&self, from: &PointFloat, to: &PointFloat) -> Polygon {
+        self.polyline(&[*from, *to], false)
+    }
+
+    pub fn polyline(&self, points: &[PointFloat], closed: bool) -> Polygon {
+        let mut points: Vec<PointFloat> = points.to_vec();
+        points.dedup_by(|a, b| (*a - *b).length() < f32::EPSILON);
+        if closed
+            && points.len() > 1
+            && (points[0] - points[points.len() - 1]).length() < f32::EPSILON
+        {
+            points.pop();
+        }
+
+        let mut contours = vec![];
+        if self.dash.iter().sum::<f32>() > 0.0 {
+            let mut path = points.clone();
+            if closed && !points.is_empty() {
+                path.push(points[0]);
+            }
+            for dash in self.split_dashes(&path) {
+                self.stroke_open(&dash, &mut contours);
+            }
+        } else if closed && points.len() > 2 {
+            self.stroke_closed(&points, &mut contours);
+        } else {
+            self.stroke_open(&points, &mut contours);
+        }
+
+        let contours = contours.into_iter().map(counter_clockwise).collect();
+        Polygon::from_path(CompoundPath::new(contours, FillRule::NonZero))
+    }
+
+    /// Strokes the outline of a polygon. Edges shared by the parts a polygon
+    /// is built from are not part of its outline and are not drawn.
+    pub fn outline(&self, polygon: &Polygon) -> Polygon {
+        let contours: Vec<Vec<PointFloat>> = polygon
+            .boundary()
+            .iter()
+            .flat_map(|contour| self.polyline(contour, true).contours())
+            .collect();
+        Polygon::from_path(CompoundPath::new(contours, FillRule::NonZero))
+    }
+
+    fn split_dashes(&self, points: &[PointFloat]) -> Vec<Vec<PointFloat>> {
+        let total: f32 = self.dash.iter().sum();
+        let mut position = self.dash_offset.rem_euclid(total);
+        let mut index = 0;
+        while position >= self.dash[index] {
+            position -= self.dash[index];
+            index = (index + 1) % self.dash.len();
+        }
+        // how much of the current dash or gap is left
+        let mut remaining = self.dash[index] - position;
+
+        let mut result = vec![];
+        let mut current = vec![];
+        if index % 2 == 0 && !points.is_empty() {
+            current.push(points[0]);
+        }
+        for pair in points.windows(2) {
+            let (start, end) = (&pair[0], &pair[1]);
+            let segment_len = (*end - *start).length();
+            let direction = (*end - *start).normalize();
+            let mut travelled = 0.0;
+            while segment_len - travelled > remaining {
+                travelled += remaining;
+                let point = *start + direction * travelled;
+                if index % 2 == 0 {
+                    current.push(point);
+                    result.push(std::mem::take(&mut current));
+                } else {
+                    current.push(point);
+                }
+                index = (index + 1) % self.dash.len();
+                remaining = self.dash[index];
+            }
+            remaining -= segment_len - travelled;
+            if index % 2 == 0 {
+                current.push(*end);
+            }
+        }
+        if index % 2 == 0 && current.len() > 1 {
+            result.push(current);
+        }
+        result
+    }
+
+    fn stroke_open(&self, points: &[PointFloat], contours: &mut Vec<Vec<PointFloat>>) {
+        let half = self.width / 2.0;
+        if points.len() < 2 {
+            if let (Some(point), LineCap::Round | LineCap::Square) = (points.first(), self.cap) {
+                self.cap_at(point, &PointFloat::new(1.0, 0.0), contours);
+                self.cap_at(point, &PointFloat::new(-1.0, 0.0), contours);
+            }
+            return;
+        }
+        for pair in points.windows(2) {
+            self.segment(&pair[0], &pair[1], contours);
+        }
+        for i in 1..points.len() - 1 {
+            self.join_at(&points[i - 1], &points[i], &points[i + 1], contours);
+        }
+
+        let first_dir = (points[0] - points[1]).normalize();
+        let last = points.len() - 1;
+        let last_dir = (points[last] - points[last - 1]).normalize();
+        if half > 0.0 {
+            self.cap_at(&points[0], &first_dir, contours);
+            self.cap_at(&points[last], &last_dir, contours);
+        }
+    }
+
+    fn stroke_closed(&self, points: &[PointFloat], contours: &mut Vec<Vec<PointFloat>>) {
+        let n = points.len();
+        for i in 0..n {
+            self.segment(&points[i], &points[(i + 1) % n], contours);
+            self.join_at(
+                &points[(i + n - 1) % n],
+                &points[i],
+                &points[(i + 1) % n],
+                contours,
+            );
+        }
+    }
+
+    fn segment(&self, from: &PointFloat, to: &PointFloat, contours: &mut Vec<Vec<PointFloat>>) {
+        let half = self.width / 2.0;
+        let n = (*to - *from).normalize().perpendicular() * half;
+        contours.push(vec![*from + n, *from - n, *to - n, *to + n]);
+    }
+
+    /// Adds the cap at an end point, where `direction` points away from the
+    /// line.
+    fn cap_at(
+        &self,
+        point: &PointFloat,
+        direction: &PointFloat,
+        contours: &mut Vec<Vec<PointFloat>>,
+    ) {
+        let half = self.width / 2.0;
+        match self.cap {
+            LineCap::Butt => {}
+            LineCap::Round => contours.extend(Conic::circle(*point, half).flatten()),
+            LineCap::Square => {
+                let n = direction.perpendicular() * half;
+                let far = *point + *direction * half;
+                contours.push(vec![*point + n, *point - n, far - n, far + n]);
+            }
+        }
+    }
+
+    fn join_at(
+        &self,
+        prev: &PointFloat,
+        point: &PointFloat,
+        next: &PointFloat,
+        contours: &mut Vec<Vec<PointFloat>>,
+    ) {
+        let half = self.width / 2.0;
+        let dir_in = (*point - *prev).normalize();
+        let dir_out = (*next - *point).normalize();
+        let turn = dir_in.cross(&dir_out);
+        if turn.abs() < 1e-6 {
+            return;
+        }
+        // the gap between the segments is on the outside of the turn
+        let side = match turn > 0.0 {
+            true => -half,
+            false => half,
+        };
+        let outer_in = *point + dir_in.perpendicular() * side;
+        let outer_out = *point + dir_out.perpendicular() * side;
+
+        match self.join {
+            LineJoin::Round => contours.extend(Conic::circle(*point, half).flatten()),
+            LineJoin::Bevel => contours.push(vec![*point, outer_in, outer_out]),
+            LineJoin::Miter => {
+                let cos_theta = -dir_in.dot(&dir_out);
+                let theta = cos_theta.clamp(-1.0, 1.0).acos();
+                let ratio = 1.0 / (theta / 2.0).sin();
+                if ratio > self.miter_limit || !ratio.is_finite() {
+                    contours.push(vec![*point, outer_in, outer_out]);
+                    return;
+                }
+                let bisector = (outer_in + outer_out - *point * 2.0).normalize();
+                let tip = *point + bisector * (half * ratio);
+                contours.push(vec![*point, outer_in, tip, outer_out]);
+            }
+        }
+    }
+}
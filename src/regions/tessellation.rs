@@ -1,4 +1,4 @@
-use super::{Point, Polygon, Rectangle};
+use super::{AntiAlias, Point, Polygon, Rectangle, TileIterator, Transform};
 
 pub type MakePolygonFn = fn(bounds: &Rectangle) -> Polygon;
 pub type CenterFn =
@@ -12,4 +12,32 @@ impl PolygonTessellation {
     pub fn new(polygons: Vec<Polygon>) -> Self {
         Self { polygons }
     }
+
+    /// Clips every polygon exactly to the bounds, dropping the ones that fall
+    /// completely outside of them.
+    pub fn clip(&self, bounds: &Rectangle) -> Self {
+        let polygons = self
+            .polygons
+            .iter()
+            .map(|polygon| polygon.clip(bounds))
+            .filter(|polygon| !polygon.is_empty())
+            .collect();
+        Self { polygons }
+    }
+
+    /// Returns the pixels of every polygon in turn, each with the index of
+    /// its polygon, its distance to the polygon's edge and its position
+    /// within the polygon.
+    pub fn iter_tiles(&self, anti_alias: AntiAlias) -> TileIterator<'_> {
+        TileIterator::new(&self.polygons, anti_alias)
+    }
+
+    pub fn transform(&self, transform: &Transform) -> Self {
+        let polygons = self
+            .polygons
+            .iter()
+            .map(|polygon| polygon.transform(transform))
+            .collect();
+        Self { polygons }
+    }
 }
@@ -0,0 +1,98 @@
+use super::{coverage::CoverageIterator, point::PointFloat, AntiAlias, Point, Polygon};
+
+/// A pixel of a tile, with what is known about its place in the tile.
+#[derive(Clone, Debug)]
+pub struct TilePoint {
+    pub point: Point,
+    /// The fraction of the pixel the tile covers.
+    pub coverage: f32,
+    /// The index of the tile's polygon in the tessellation.
+    pub polygon: usize,
+    /// The distance from the pixel center to the nearest edge of the tile.
+    pub edge_distance: f32,
+    /// The pixel center relative to the bounds of the tile, from 0 at the
+    /// smallest x and y to 1 at the largest. The image is written with y = 0
+    /// as its bottom row, so that is the bottom left of the tile.
+    pub local: PointFloat,
+}
+
+/// The outline of the tile being walked, kept while its pixels are returned.
+struct Tile<'a> {
+    index: usize,
+    points: CoverageIterator<'a>,
+    edges: Vec<(PointFloat, PointFloat)>,
+    origin: PointFloat,
+    size: PointFloat,
+}
+
+impl<'a> Tile<'a> {
+    fn new(index: usize, polygon: &'a Polygon, anti_alias: AntiAlias) -> Self {
+        let mut edges = vec![];
+        for contour in polygon.boundary() {
+            for i in 0..contour.len() {
+                edges.push((contour[i], contour[(i + 1) % contour.len()]));
+            }
+        }
+        let bounds = polygon.bounds();
+        Self {
+            index,
+            points: polygon.iter_coverage(anti_alias),
+            edges,
+            origin: bounds.origin.to_float(),
+            size: PointFloat::new(bounds.size.x.max(1) as f32, bounds.size.y.max(1) as f32),
+        }
+    }
+
+    fn edge_distance(&self, center: &PointFloat) -> f32 {
+        self.edges
+            .iter()
+            .map(|(from, to)| center.distance_to_segment(from, to))
+            .fold(f32::MAX, f32::min)
+    }
+}
+
+/// Returns every pixel of every polygon in turn, like `iter_coverage`, along
+/// with the index of its polygon, its distance to the polygon's edge and its
+/// position within the polygon's bounds.
+pub struct TileIterator<'a> {
+    polygons: &'a [Polygon],
+    anti_alias: AntiAlias,
+    next_index: usize,
+    tile: Option<Tile<'a>>,
+}
+
+impl<'a> TileIterator<'a> {
+    pub(crate) fn new(polygons: &'a [Polygon], anti_alias: AntiAlias) -> Self {
+        Self {
+            polygons,
+            anti_alias,
+            next_index: 0,
+            tile: None,
+        }
+    }
+}
+
+impl<'a> Iterator for TileIterator<'a> {
+    type Item = TilePoint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(tile) = &mut self.tile {
+                if let Some((point, coverage)) = tile.points.next() {
+                    let center = PointFloat::pixel_center(&point);
+                    let offset = center - tile.origin;
+                    return Some(TilePoint {
+                        coverage,
+                        polygon: tile.index,
+                        edge_distance: tile.edge_distance(&center),
+                        local: PointFloat::new(offset.x / tile.size.x, offset.y / tile.size.y),
+                        point,
+                    });
+                }
+            }
+            let polygon = self.polygons.get(self.next_index)?;
+            self.tile = Some(Tile::new(self.next_index, polygon, self.anti_alias));
+            self.next_index += 1;
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use super::{point::PointFloat, Point};
+
+/// A 2D affine transform, mapping `(x, y)` to
+/// `(a * x + c * y + e, b * x + d * y + f)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Transform {
+    pub const fn identity() -> Self {
+        Self {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self {
+            e: x,
+            f: y,
+            ..Self::identity()
+        }
+    }
+
+    /// Rotates counter clockwise around the origin, in degrees.
+    pub fn rotate(degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Self::identity()
+        }
+    }
+
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self {
+            a: x,
+            d: y,
+            ..Self::identity()
+        }
+    }
+
+    /// Shears along the x and y axes by the given angles, in degrees.
+    pub fn skew(x_degrees: f32, y_degrees: f32) -> Self {
+        Self {
+            b: y_degrees.to_radians().tan(),
+            c: x_degrees.to_radians().tan(),
+            ..Self::identity()
+        }
+    }
+
+    /// Returns the transform that applies `self` first and `next` after it.
+    pub fn then(&self, next: &Transform) -> Self {
+        Self {
+            a: next.a * self.a + next.c * self.b,
+            b: next.b * self.a + next.d * self.b,
+            c: next.a * self.c + next.c * self.d,
+            d: next.b * self.c + next.d * self.d,
+            e: next.a * self.e + next.c * self.f + next.e,
+            f: next.b * self.e + next.d * self.f + next.f,
+        }
+    }
+
+    /// The transform that moves the three points of `from` onto the three
+    /// points of `to`. Returns `None` when the points of `from` lie on a line.
+    pub fn from_triangles(from: [PointFloat; 3], to: [PointFloat; 3]) -> Option<Self> {
+        // maps the unit axes onto the two edges from the first point
+        let from_unit = |[p1, p2, p3]: [PointFloat; 3]| Self {
+            a: p2.x - p1.x,
+            b: p2.y - p1.y,
+            c: p3.x - p1.x,
+            d: p3.y - p1.y,
+            e: p1.x,
+            f: p1.y,
+        };
+        Some(from_unit(from).invert()?.then(&from_unit(to)))
+    }
+
+    /// The transform that moves every point of `from` onto the point of `to`
+    /// at the same index, fitted to the first three points of `from` that
+    /// don't lie on a line. Returns `None` when there are no such points.
+    pub fn from_points(from: &[PointFloat], to: &[PointFloat]) -> Option<Self> {
+        let count = from.len().min(to.len());
+        for j in 1..count {
+            for k in j + 1..count {
+                let fitted =
+                    Self::from_triangles([from[0], from[j], from[k]], [to[0], to[j], to[k]]);
+                if fitted.is_some() {
+                    return fitted;
+                }
+            }
+        }
+        None
+    }
+
+    /// Applies the transform as if `center` were the origin, for example to
+    /// rotate or scale a shape in place.
+    pub fn around(&self, center: &PointFloat) -> Self {
+        Self::translate(-center.x, -center.y)
+            .then(self)
+            .then(&Self::translate(center.x, center.y))
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// Returns `None` when the transform collapses the plane onto a line or
+    /// a point.
+    pub fn invert(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+        Some(Self {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        })
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    pub fn apply(&self, point: &PointFloat) -> PointFloat {
+        PointFloat::new(
+            self.a * point.x + self.c * point.y + self.e,
+            self.b * point.x + self.d * point.y + self.f,
+        )
+    }
+
+    pub fn apply_point(&self, point: &Point) -> PointFloat {
+        self.apply(&point.to_float())
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
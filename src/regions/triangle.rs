@@ -5,9 +5,6 @@ use super::{
     Rectangle,
 };
 
-const DISABLE_SKIP: bool = false;
-const ANNOTATE: bool = true;
-
 #[derive(Clone, Copy, Debug)]
 pub enum Orientation {
     Up,
@@ -16,526 +13,199 @@ pub enum Orientation {
     Right,
 }
 
-#[derive(Clone)]
-pub struct StandardTriangle {
-    p1: Point,
-    p2: Point,
-    p3: Point,
-    orientation: Orientation,
-    is_transposed: bool,
-}
-
-const PERMUTATIONS: [[usize; 3]; 6] = [
-    [0, 1, 2],
-    [0, 2, 1],
-    [1, 0, 2],
-    [1, 2, 0],
-    [2, 0, 1],
-    [2, 1, 0],
-];
-
-const ORIENTATIONS: [Orientation; 4] = [
-    Orientation::Up,
-    Orientation::Down,
-    Orientation::Right,
-    Orientation::Left,
-];
-
-const PI: f32 = 3.14159;
-
-impl StandardTriangle {
-    pub fn new(p1: Point, p2: Point, p3: Point, orientation: Orientation) -> Self {
-        assert!(Self::valid_orientation(&p1, &p2, &p3, orientation));
-        Self {
-            p1,
-            p2,
-            p3,
-            orientation,
-            is_transposed: false,
-        }
-    }
-
-    pub fn valid_orientation(p1: &Point, p2: &Point, p3: &Point, orientation: Orientation) -> bool {
-        match orientation {
+impl Orientation {
+    /// Checks that the base from `p1` to `p2` is parallel to an axis, runs
+    /// in the positive direction, and that `p3` lies on this side of it.
+    pub fn is_valid(&self, p1: &PointFloat, p2: &PointFloat, p3: &PointFloat) -> bool {
+        match self {
             Orientation::Up => p1.y == p2.y && p3.y > p1.y && p1.x < p2.x,
             Orientation::Down => p1.y == p2.y && p3.y < p1.y && p1.x < p2.x,
             Orientation::Left => p1.x == p2.x && p3.x < p1.x && p1.y < p2.y,
             Orientation::Right => p1.x == p2.x && p3.x > p1.x && p1.y < p2.y,
         }
     }
-
-    pub fn try_new(p1: &Point, p2: &Point, p3: &Point) -> Option<Self> {
-        let pts = [p1, p2, p3];
-        for orientation in ORIENTATIONS.iter() {
-            for perm in PERMUTATIONS.iter() {
-                let tp1 = pts[perm[0]];
-                let tp2 = pts[perm[1]];
-                let tp3 = pts[perm[2]];
-                if Self::valid_orientation(tp1, tp2, tp3, *orientation) {
-                    return Some(Self::new(
-                        tp1.clone(),
-                        tp2.clone(),
-                        tp3.clone(),
-                        *orientation,
-                    ));
-                }
-            }
-        }
-        None
-    }
-
-    pub fn height(&self) -> i32 {
-        match self.orientation {
-            Orientation::Up | Orientation::Down => self.p3.y.abs_diff(self.p2.y) as i32,
-            Orientation::Left | Orientation::Right => self.p3.x.abs_diff(self.p2.x) as i32,
-        }
-    }
-
-    pub fn bounds(&self) -> Rectangle {
-        Rectangle::from_bounds(
-            Point::new(
-                self.p1.x.min(self.p2.x).min(self.p3.x),
-                self.p1.y.min(self.p2.y).min(self.p3.y),
-            ),
-            Point::new(
-                self.p1.x.max(self.p2.x).max(self.p3.x),
-                self.p1.y.max(self.p2.y).max(self.p3.y),
-            ),
-        )
-    }
-
-    pub fn transpose(&self) -> Self {
-        assert!(!self.is_transposed);
-        match self.orientation {
-            Orientation::Up => Self {
-                p1: self.p1.clone(),
-                p2: self.p2.clone(),
-                p3: self.p3.clone(),
-                orientation: self.orientation,
-                is_transposed: true,
-            },
-            Orientation::Down => Self {
-                p1: self.p1.clone(),
-                p2: self.p2.clone(),
-                p3: Point::new(self.p3.x, self.p3.y + 2 * self.height()),
-                orientation: self.orientation,
-                is_transposed: true,
-            },
-            Orientation::Left => Self {
-                p1: Point::new(self.p1.y, self.p1.x),
-                p2: Point::new(self.p2.y, self.p2.x),
-                p3: Point::new(self.p3.y, self.p3.x + 2 * self.height()),
-                orientation: self.orientation,
-                is_transposed: true,
-            },
-            Orientation::Right => Self {
-                p1: Point::new(self.p1.y, self.p1.x),
-                p2: Point::new(self.p2.y, self.p2.x),
-                p3: Point::new(self.p3.y, self.p3.x),
-                orientation: self.orientation,
-                is_transposed: true,
-            },
-        }
-    }
-
-    pub fn untranspose(&self, point: Point) -> Point {
-        assert!(self.is_transposed);
-        match self.orientation {
-            Orientation::Up => point,
-            Orientation::Down => {
-                let mut p = point;
-                p.y -= (2 * p.y.abs_diff(self.p1.y)) as i32;
-                p
-            }
-            Orientation::Left => {
-                let dist = point.y.abs_diff(self.p1.y) as i32;
-                let res = Point::new(point.y - 2 * dist, point.x);
-                res
-            }
-            Orientation::Right => Point::new(point.y, point.x),
-        }
-    }
-
-    pub fn iter_points<'a>(&'a self) -> StandardTriangleIterator<'a> {
-        StandardTriangleIterator::new(self)
-    }
 }
 
-impl Debug for StandardTriangle {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "p1={:?}, p2={:?}, p3={:?}, orientation: {:?}",
-            self.p1, self.p2, self.p3, self.orientation
-        )
-    }
+/// Any triangle, with its vertices stored counter clockwise.
+///
+/// A pixel belongs to the triangle when its center is inside. Centers exactly
+/// on an edge follow the top-left rule, where top means the smallest y: they
+/// belong to the triangle when the edge is a left edge or a horizontal edge
+/// at the top. A shared edge is a left edge of one triangle and a right edge
+/// of the other, so triangles that share edges cover every pixel exactly
+/// once.
+#[derive(Clone)]
+pub struct Triangle {
+    p1: PointFloat,
+    p2: PointFloat,
+    p3: PointFloat,
 }
 
-pub struct StandardTriangleIterator<'a> {
-    triangle: &'a StandardTriangle,
-    transposed_triange: StandardTriangle,
-    inv_slope_left: f32,
-    inv_slope_right: f32,
-    cur_left: PointFloat,
-    cur_right: PointFloat,
-    cur_point: Point,
-    cur_annotation: PointAnnotation,
-    done: bool,
+/// The edge function of the edge from `a` to `b`, which is positive when the
+/// point is to the left of the edge and zero when it is on it.
+fn edge_function(a: &PointFloat, b: &PointFloat, point: &PointFloat) -> f32 {
+    (*b - *a).cross(&(*point - *a))
 }
 
-impl<'a> StandardTriangleIterator<'a> {
-    fn new(triangle: &'a StandardTriangle) -> Self {
-        let transposed_triange = triangle.transpose();
-
-        let p3_f = transposed_triange.p3.to_float();
-        let cur_left = transposed_triange.p1.to_float();
-        let cur_right = transposed_triange.p2.to_float();
-        let cur_point = transposed_triange.p1.clone();
-
-        let mut result = Self {
-            triangle,
-            transposed_triange,
-            inv_slope_left: cur_left.inv_slope(&p3_f),
-            inv_slope_right: -1.0 * p3_f.inv_slope(&cur_right),
-            cur_left,
-            cur_right,
-            cur_point,
-            cur_annotation: PointAnnotation::FullEdge,
-            done: false,
-        };
-
-        result.fast_forward_to_start();
-
-        result
-    }
-
-    fn is_bot(&self) -> bool {
-        self.cur_point.y == self.transposed_triange.p1.y
-    }
+fn is_top_left(a: &PointFloat, b: &PointFloat) -> bool {
+    let edge = *b - *a;
+    // with counter clockwise vertices the interior is to the left of every
+    // edge, so left edges run towards -y and the top edge runs towards +x
+    edge.y < 0.0 || (edge.y == 0.0 && edge.x > 0.0)
+}
 
-    fn is_top(&self) -> bool {
-        self.cur_point.y == self.transposed_triange.p3.y
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point) -> Self {
+        Self::new_float(p1.to_float(), p2.to_float(), p3.to_float())
     }
 
-    fn fast_forward_to_start(&mut self) {
-        if ANNOTATE {
-            self.advance_x(true);
-            return;
-        }
-        if self.skip_bot() {
-            self.advance_y();
-        } else if self.skip_left() {
-            self.advance_x(false);
+    /// A triangle at a sub pixel position. The points may be given in either
+    /// winding order.
+    pub fn new_float(p1: PointFloat, p2: PointFloat, p3: PointFloat) -> Self {
+        match (p2 - p1).cross(&(p3 - p1)) < 0.0 {
+            true => Self { p1, p2: p3, p3: p2 },
+            false => Self { p1, p2, p3 },
         }
     }
 
-    fn advance_x(&mut self, allow_adv_y: bool) {
-        self.cur_point.x += 1;
-        let bound;
-        if ANNOTATE {
-            bound = self.cur_right.x.round();
-            if self.is_bot() {
-                if self.skip_bot() {
-                    self.cur_annotation = PointAnnotation::FullEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                } else {
-                    self.cur_annotation = PointAnnotation::StandardEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                }
-            } else if self.is_top() {
-                if self.skip_top() {
-                    self.cur_annotation = PointAnnotation::FullEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                } else {
-                    self.cur_annotation = PointAnnotation::StandardEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                }
-            } else if self.cur_point.x == bound as i32 {
-                if self.skip_right() {
-                    self.cur_annotation = PointAnnotation::FullEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                } else {
-                    self.cur_annotation = PointAnnotation::StandardEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                }
-            } else {
-                self.cur_annotation = PointAnnotation::Regular;
-                println!("Setting annotation to {:?}", self.cur_annotation);
-            }
-        } else {
-            bound = match self.skip_right() {
-                false => self.cur_right.x.round(),
-                true => self.cur_right.x.round() - 1.0,
-            };
-        }
-
-        if self.cur_point.x as f32 > bound {
-            if allow_adv_y {
-                self.advance_y();
-            } else {
-                self.done = true;
-            }
-        }
+    /// A triangle with its base from `p1` to `p2` parallel to an axis.
+    pub fn new_standard(p1: Point, p2: Point, p3: Point, orientation: Orientation) -> Self {
+        let (p1, p2, p3) = (p1.to_float(), p2.to_float(), p3.to_float());
+        assert!(orientation.is_valid(&p1, &p2, &p3));
+        Self::new_float(p1, p2, p3)
     }
 
-    fn advance_y(&mut self) {
-        self.cur_left.y += 1.0;
-        self.cur_left.x += self.inv_slope_left;
-
-        self.cur_right.y += 1.0;
-        self.cur_right.x -= self.inv_slope_right;
-
-        self.cur_point = self.cur_left.round();
-
-        let bound;
-        if ANNOTATE {
-            bound = self.cur_right.x;
-            if self.is_bot() {
-                if self.skip_bot() {
-                    self.cur_annotation = PointAnnotation::FullEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                } else {
-                    self.cur_annotation = PointAnnotation::StandardEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                }
-            } else if self.is_top() {
-                if self.skip_top() {
-                    self.cur_annotation = PointAnnotation::FullEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                } else {
-                    self.cur_annotation = PointAnnotation::StandardEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                }
-            } else {
-                if self.skip_left() {
-                    self.cur_annotation = PointAnnotation::FullEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                } else {
-                    self.cur_annotation = PointAnnotation::StandardEdge;
-                    println!("Setting annotation to {:?}", self.cur_annotation);
-                }
-            }
-        } else {
-            bound = match self.skip_top() {
-                false => self.cur_right.x,
-                true => self.cur_right.x - 1.0,
-            };
-        }
-
-        if self.cur_left.x > bound {
-            self.done = true;
-        }
-
-        if ANNOTATE {
-            if self.skip_left() {
-                self.advance_x(false);
-            }
-        }
+    pub fn vertices(&self) -> [PointFloat; 3] {
+        [self.p1, self.p2, self.p3]
     }
 
-    fn skip_left(&self) -> bool {
-        if DISABLE_SKIP {
-            return false;
-        }
-        return match self.triangle.orientation {
-            Orientation::Up => self.triangle.p3.x > self.triangle.p1.x,
-            Orientation::Down => self.triangle.p3.x < self.triangle.p1.x,
-            Orientation::Left => false,
-            Orientation::Right => false,
-        };
+    pub fn area(&self) -> f32 {
+        (self.p2 - self.p1).cross(&(self.p3 - self.p1)) / 2.0
     }
 
-    fn skip_right(&self) -> bool {
-        if DISABLE_SKIP {
-            return false;
-        }
-        return match self.triangle.orientation {
-            Orientation::Up => self.triangle.p3.x < self.triangle.p2.x,
-            Orientation::Down => self.triangle.p3.x > self.triangle.p2.x,
-            Orientation::Left => true,
-            Orientation::Right => true,
-        };
+    /// The smallest pixel aligned rectangle around the triangle.
+    pub fn bounds(&self) -> Rectangle {
+        let vertices = self.vertices();
+        let min_x = vertices.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+        let min_y = vertices.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        let max_x = vertices.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+        let max_y = vertices.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        Rectangle::from_bounds(
+            Point::new(min_x.floor() as i32, min_y.floor() as i32),
+            Point::new(max_x.ceil() as i32, max_y.ceil() as i32),
+        )
     }
 
-    fn skip_top(&self) -> bool {
-        if DISABLE_SKIP {
-            return false;
-        }
-        match self.triangle.orientation {
-            Orientation::Up => true,
-            Orientation::Down => false,
-            Orientation::Left => false,
-            Orientation::Right => false,
-        }
+    /// Whether the point is inside or on any edge of the triangle.
+    pub fn contains(&self, point: &PointFloat) -> bool {
+        let [a, b, c] = self.vertices();
+        edge_function(&a, &b, point) >= 0.0
+            && edge_function(&b, &c, point) >= 0.0
+            && edge_function(&c, &a, point) >= 0.0
     }
 
-    fn skip_bot(&self) -> bool {
-        if DISABLE_SKIP {
+    /// Whether the point is inside under the top-left rule.
+    pub fn covers(&self, point: &PointFloat) -> bool {
+        if self.area() <= 0.0 {
             return false;
         }
-        match self.triangle.orientation {
-            Orientation::Up => false,
-            Orientation::Down => true,
-            Orientation::Left => false,
-            Orientation::Right => false,
-        }
+        let [a, b, c] = self.vertices();
+        [(a, b), (b, c), (c, a)].iter().all(|(from, to)| {
+            let value = edge_function(from, to, point);
+            value > 0.0 || (value == 0.0 && is_top_left(from, to))
+        })
     }
-}
 
-impl<'a> Iterator for StandardTriangleIterator<'a> {
-    type Item = Point;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.done {
-            return None;
+    /// The weights of the three vertices, in the order of `vertices`, that
+    /// add up to the point. They sum to 1, and are all between 0 and 1 when
+    /// the point is inside.
+    pub fn barycentric(&self, point: &PointFloat) -> [f32; 3] {
+        let [a, b, c] = self.vertices();
+        let double_area = 2.0 * self.area();
+        if double_area == 0.0 {
+            return [1.0, 0.0, 0.0];
         }
-
-        let result = self
-            .transposed_triange
-            .untranspose(self.cur_point.clone())
-            .annotate(self.cur_annotation.clone());
-        self.advance_x(true);
-
-        println!("returning point with annotation: {:?}", self.cur_annotation);
-        Some(result)
+        [
+            edge_function(&b, &c, point) / double_area,
+            edge_function(&c, &a, point) / double_area,
+            edge_function(&a, &b, point) / double_area,
+        ]
     }
-}
 
-pub struct Triangle {
-    t1: StandardTriangle,
-    t2: Option<StandardTriangle>,
-}
-
-fn prepare_point_order(p1: Point, p2: Point, p3: Point) -> (Point, Point, Point) {
-    let mut pts = [p1, p2, p3];
-    if pts[0].y > pts[1].y {
-        pts.swap(0, 1);
-    }
-    if pts[1].y > pts[2].y {
-        pts.swap(1, 2);
-    }
-    if pts[0].y > pts[1].y {
-        pts.swap(0, 1);
+    pub fn iter_points<'a>(&'a self) -> TriangleIterator<'a> {
+        TriangleIterator::new(self)
     }
-
-    let [p3, p1, p2] = pts;
-    (p1, p2, p3)
 }
 
-impl Triangle {
-    pub fn new_standard(p1: Point, p2: Point, p3: Point, orientation: Orientation) -> Self {
-        let t1 = StandardTriangle::new(p1, p2, p3, orientation);
-        Self { t1, t2: None }
-    }
-
-    pub fn new(p1: Point, p2: Point, p3: Point) -> Self {
-        println!(
-            "generating triangles for points: p1={:?}, p2={:?}, p3={:?}",
-            p1, p2, p3
-        );
-        if let Some(t1) = StandardTriangle::try_new(&p1, &p2, &p3) {
-            println!(
-                "found simple triangle: p1={:?}, p2={:?}, p3={:?}, orientation: {:?}",
-                t1.p1, t1.p2, t1.p3, t1.orientation
-            );
-            return Self { t1, t2: None };
-        }
-        let (p1, p2, p3) = prepare_point_order(p1, p2, p3);
-
-        // lets start with the upper triangle:
-        let seg_p1_p2 = Point::new(p2.x - p1.x, p2.y - p1.y);
-        let swap_base = seg_p1_p2.x < 0;
-        let sign_mult = match swap_base {
-            true => -1.0,
-            false => 1.0,
-        };
-        let angle_p2_p1_x;
-        if seg_p1_p2.x < 0 {
-            angle_p2_p1_x = f32::atan(seg_p1_p2.y as f32 / (-1 * seg_p1_p2.x) as f32);
-        } else {
-            angle_p2_p1_x = f32::atan(seg_p1_p2.y as f32 / seg_p1_p2.x as f32);
-        }
-        let seg_p3_p2 = Point::new(p2.x - p3.x, p2.y - p3.y);
-        let angle_p1_p2_x = (seg_p1_p2.dot(&seg_p3_p2) as f32
-            / (seg_p1_p2.magnitude() * seg_p3_p2.magnitude()))
-        .acos();
-
-        let angle_p2_x_p1 = PI - angle_p1_p2_x - angle_p2_p1_x;
-
-        let seg_p1_x_x =
-            sign_mult * angle_p1_p2_x.sin() * seg_p1_p2.magnitude() / angle_p2_x_p1.sin();
-
-        let p4 = Point::new(p1.x + seg_p1_x_x.round() as i32, p1.y);
-
-        let t1 = match swap_base {
-            false => StandardTriangle::new(p1.clone(), p4.clone(), p2, Orientation::Up),
-            true => StandardTriangle::new(p4.clone(), p1.clone(), p2, Orientation::Up),
-        };
-
-        let t2 = match swap_base {
-            false => StandardTriangle::new(p1, p4, p3, Orientation::Down),
-            true => StandardTriangle::new(p4, p1, p3, Orientation::Down),
-        };
-
-        println!(
-            "first: p1={:?}, p2={:?}, p3={:?}, orientation: {:?}",
-            t1.p1, t1.p2, t1.p3, t1.orientation
-        );
-        println!(
-            "secon: p1={:?}, p2={:?}, p3={:?}, orientation: {:?}",
-            t2.p1, t2.p2, t2.p3, t2.orientation
-        );
-
-        Self { t1, t2: Some(t2) }
-    }
-
-    pub fn to_triangles(self) -> (StandardTriangle, Option<StandardTriangle>) {
-        (self.t1, self.t2)
-    }
-
-    pub fn to_triangle_vec(self) -> Vec<StandardTriangle> {
-        let mut result = vec![self.t1];
-        if let Some(t2) = self.t2 {
-            result.push(t2);
-        }
-        result
-    }
-
-    pub fn iter_points<'a>(&'a self) -> TriangleIterator<'a> {
-        TriangleIterator::new(self)
+impl Debug for Triangle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "p1={:?}, p2={:?}, p3={:?}", self.p1, self.p2, self.p3)
     }
 }
 
+/// Walks the rows of the triangle's bounds and returns every pixel the
+/// triangle covers. A triangle is convex, so the covered pixels of a row are
+/// always a single run. The first and last pixel of every run are annotated as
+/// edge pixels.
 pub struct TriangleIterator<'a> {
     triangle: &'a Triangle,
-    cur_iter: StandardTriangleIterator<'a>,
-    on_t2: bool,
+    bounds: Rectangle,
+    y: i32,
+    x: i32,
+    span_start: i32,
+    span_end: i32,
 }
 
 impl<'a> TriangleIterator<'a> {
-    pub fn new(triangle: &'a Triangle) -> Self {
+    fn new(triangle: &'a Triangle) -> Self {
+        let bounds = triangle.bounds();
         Self {
             triangle,
-            cur_iter: triangle.t1.iter_points(),
-            on_t2: false,
+            y: bounds.origin.y - 1,
+            bounds,
+            x: 0,
+            span_start: 0,
+            span_end: 0,
+        }
+    }
+
+    fn covers(&self, x: i32) -> bool {
+        let center = PointFloat::pixel_center(&Point::new(x, self.y));
+        self.triangle.covers(&center)
+    }
+
+    /// Finds the run of covered pixels in the current row.
+    fn load_row(&mut self) {
+        let end_x = self.bounds.extent().x;
+        let mut x = self.bounds.origin.x;
+        while x < end_x && !self.covers(x) {
+            x += 1;
         }
+        self.span_start = x;
+        while x < end_x && self.covers(x) {
+            x += 1;
+        }
+        self.span_end = x;
+        self.x = self.span_start;
     }
 }
 
 impl<'a> Iterator for TriangleIterator<'a> {
     type Item = Point;
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(next_val) = self.cur_iter.next() {
-            return Some(next_val);
-        }
-        if !self.on_t2 {
-            self.on_t2 = true;
-            if let Some(t2) = &self.triangle.t2 {
-                self.cur_iter = t2.iter_points();
-                if let Some(next_val) = self.cur_iter.next() {
-                    return Some(next_val);
-                }
+        while self.x >= self.span_end {
+            self.y += 1;
+            if self.y >= self.bounds.extent().y {
+                return None;
             }
+            self.load_row();
         }
-        None
+        let annotation = match self.x == self.span_start || self.x == self.span_end - 1 {
+            true => PointAnnotation::StandardEdge,
+            false => PointAnnotation::Regular,
+        };
+        let result = Point::new(self.x, self.y).annotate(annotation);
+        self.x += 1;
+        Some(result)
     }
 }
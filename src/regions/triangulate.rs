@@ -0,0 +1,116 @@
+use super::Point;
+
+fn cross(o: &Point, a: &Point, b: &Point) -> i64 {
+    (a.x - o.x) as i64 * (b.y - o.y) as i64 - (a.y - o.y) as i64 * (b.x - o.x) as i64
+}
+
+pub fn signed_area_2x(points: &[Point]) -> i64 {
+    let mut area = 0;
+    for i in 0..points.len() {
+        let p1 = &points[i];
+        let p2 = &points[(i + 1) % points.len()];
+        area += p1.x as i64 * p2.y as i64 - p2.x as i64 * p1.y as i64;
+    }
+    area
+}
+
+/// Drops repeated vertices and vertices that lie on a straight line between
+/// their neighbors; neither changes the outline and both break ear clipping.
+fn simplify(points: &[Point]) -> Vec<Point> {
+    let mut result: Vec<Point> = vec![];
+    for point in points {
+        let point = Point::new(point.x, point.y);
+        if result.last() != Some(&point) {
+            result.push(point);
+        }
+    }
+    while result.len() > 1 && result.first() == result.last() {
+        result.pop();
+    }
+
+    let mut changed = true;
+    while changed && result.len() >= 3 {
+        changed = false;
+        for i in 0..result.len() {
+            let prev = &result[(i + result.len() - 1) % result.len()];
+            let next = &result[(i + 1) % result.len()];
+            if cross(prev, &result[i], next) == 0 {
+                result.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    result
+}
+
+fn in_triangle(p: &Point, a: &Point, b: &Point, c: &Point) -> bool {
+    // the triangle is counter clockwise, points on an edge count as inside
+    cross(a, b, p) >= 0 && cross(b, c, p) >= 0 && cross(c, a, p) >= 0
+}
+
+/// Triangulates a simple polygon, convex or concave, by ear clipping. The
+/// vertices may be in either winding order.
+pub fn ear_clip(points: &[Point]) -> Vec<[Point; 3]> {
+    let mut remaining = simplify(points);
+    if remaining.len() < 3 {
+        return vec![];
+    }
+    if signed_area_2x(&remaining) < 0 {
+        remaining.reverse();
+    }
+
+    let mut result = vec![];
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let prev = &remaining[(i + n - 1) % n];
+            let cur = &remaining[i];
+            let next = &remaining[(i + 1) % n];
+            if cross(prev, cur, next) <= 0 {
+                return false;
+            }
+            !remaining.iter().enumerate().any(|(j, p)| {
+                j != i
+                    && j != (i + n - 1) % n
+                    && j != (i + 1) % n
+                    && p != prev
+                    && p != cur
+                    && p != next
+                    && in_triangle(p, prev, cur, next)
+            })
+        });
+        // a polygon that is not simple may have no ear left, in which case
+        // the best we can do is clip the next convex vertex
+        let i = ear.unwrap_or_else(|| {
+            (0..n)
+                .find(|&i| {
+                    cross(
+                        &remaining[(i + n - 1) % n],
+                        &remaining[i],
+                        &remaining[(i + 1) % n],
+                    ) > 0
+                })
+                .unwrap_or(0)
+        });
+        let prev = remaining[(i + n - 1) % n].clone();
+        let next = remaining[(i + 1) % n].clone();
+        let cur = remaining.remove(i);
+        if cross(&prev, &cur, &next) != 0 {
+            result.push([prev, cur, next]);
+        }
+        remaining = simplify(&remaining);
+        if remaining.len() < 3 {
+            return result;
+        }
+    }
+    let [a, b, c] = [
+        remaining[0].clone(),
+        remaining[1].clone(),
+        remaining[2].clone(),
+    ];
+    if cross(&a, &b, &c) != 0 {
+        result.push([a, b, c]);
+    }
+    result
+}
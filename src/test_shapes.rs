@@ -1,10 +1,23 @@
-use crate::images::Pixel;
-use crate::polygons::{Hexagon, RectanglePoly};
+use std::rc::Rc;
+
+use crate::images::{ColorSpace, Gradient, Image, Pixel, Sampling};
+use crate::polygons::{
+    Annulus, ArcBand, Circle, Delaunay, Ellipse, Hexagon, RectanglePoly, RegularPolygon, Sector,
+    Star, Truchet, TruchetStyle, Wang,
+};
 use crate::regions::tess::PolygonTessellation;
-use crate::regions::{Orientation, Point, Polygon, Rectangle, Region};
+use crate::regions::{
+    AntiAlias, Conic, FillRule, LineCap, LineJoin, Orientation, Path, Point, PointFloat, Polygon,
+    Rectangle, Region, Sdf, Stroke, TilePoint, Transform,
+};
 
+use crate::palette::{Average, Quantizer};
+use crate::random::Rng;
 use crate::transformers::{
-    ColorWaveTF, ColoredTessellationTF, SolidColorPolygon, SolidRectangle, SolidTriangle,
+    BlendedTessellationTF, ColorWaveTF, ColoredTessellationTF, KaleidoscopeTF, LowPolyTF,
+    PaletteMapTF, PointSource, SampledTessellationTF, ShadedMesh, ShadedTriangle,
+    SolidColorPolygon, SolidRectangle, SolidSdf, SolidTriangle, Symmetry, TexturedPolygon,
+    TexturedTessellationTF, TileBorder, TileShaderFn, TileShaderTF, WallpaperGroup, WallpaperTF,
 };
 use crate::{curves, make_regions};
 
@@ -12,6 +25,10 @@ fn pt(x: i32, y: i32) -> Point {
     Point::new(x, y)
 }
 
+fn ptf(x: f32, y: f32) -> PointFloat {
+    PointFloat::new(x, y)
+}
+
 pub fn test_octogon() -> Vec<Box<dyn Region>> {
     let center = pt(300, 400);
     let x_step = 100;
@@ -28,32 +45,10 @@ pub fn test_octogon() -> Vec<Box<dyn Region>> {
         pt(x_start - 1 * x_step, y_start + 3 * y_step),
         pt(x_start - 1 * x_step, y_start + 1 * y_step),
     ];
-    make_regions![
-        // center point: 150, 200
-        // height: 100
-        ColoredTessellationTF::descrete(
-            PolygonTessellation::new(vec![
-                Polygon::triangle(pts[0].clone(), pts[1].clone(), center.clone()),
-                Polygon::triangle(pts[1].clone(), pts[2].clone(), center.clone()),
-                Polygon::triangle(pts[2].clone(), pts[3].clone(), center.clone()),
-                Polygon::triangle(pts[3].clone(), pts[4].clone(), center.clone()),
-                Polygon::triangle(pts[4].clone(), pts[5].clone(), center.clone()),
-                Polygon::triangle(pts[5].clone(), pts[6].clone(), center.clone()),
-                Polygon::triangle(pts[6].clone(), pts[7].clone(), center.clone()),
-                Polygon::triangle(pts[7].clone(), pts[0].clone(), center.clone()),
-            ]),
-            vec![
-                Pixel::hsl(000.0, 1.0, 0.5),
-                Pixel::hsl(100.0, 1.0, 0.5),
-                Pixel::hsl(200.0, 1.0, 0.5),
-                Pixel::hsl(300.0, 1.0, 0.5),
-                Pixel::hsl(150.0, 1.0, 0.5),
-                Pixel::hsl(350.0, 1.0, 0.5),
-                Pixel::hsl(050.0, 1.0, 0.5),
-                Pixel::hsl(250.0, 1.0, 0.5),
-            ],
-        )
-    ]
+    make_regions![SolidColorPolygon::new(
+        Polygon::from_vertices(&pts),
+        Pixel::hsl(200.0, 1.0, 0.5),
+    )]
 }
 
 pub fn test_gradient() -> Vec<Box<dyn Region>> {
@@ -163,3 +158,999 @@ pub fn test_tessellation() -> Vec<Box<dyn Region>> {
         )
     ]
 }
+
+pub fn test_low_poly() -> Vec<Box<dyn Region>> {
+    make_regions![
+        ColorWaveTF::new(
+            curves::radiate_linear(0.0, 360.0),
+            curves::constant(0.5),
+            curves::radiate(0.6, 0.5),
+        ),
+        LowPolyTF::new(PointSource::Edges(600), 7)
+    ]
+}
+
+pub fn test_truchet() -> Vec<Box<dyn Region>> {
+    make_regions![
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(Truchet::tessellate(
+                &pt(300, 400),
+                60,
+                &Rectangle::new(pt(0, 0), pt(600, 260)),
+                TruchetStyle::Arcs,
+                12,
+                1,
+            )),
+            vec![Pixel::hsl(210.0, 1.0, 0.5)],
+        ),
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(Truchet::tessellate(
+                &pt(300, 400),
+                40,
+                &Rectangle::new(pt(0, 270), pt(600, 260)),
+                TruchetStyle::Triangles,
+                0,
+                2,
+            )),
+            vec![Pixel::hsl(120.0, 1.0, 0.4)],
+        ),
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(Wang::tessellate(
+                &pt(300, 400),
+                40,
+                &Rectangle::new(pt(0, 540), pt(600, 260)),
+                10,
+                3,
+            )),
+            vec![Pixel::hsl(270.0, 1.0, 0.6)],
+        )
+    ]
+}
+
+pub fn test_wallpaper() -> Vec<Box<dyn Region>> {
+    make_regions![
+        ColorWaveTF::new(
+            curves::constant(40.0),
+            curves::constant(0.3),
+            curves::linear_y(0.2, 0.4),
+        ),
+        WallpaperTF::new(
+            Box::new(SolidTriangle::new_any(
+                pt(10, 5),
+                pt(45, 15),
+                pt(20, 35),
+                Pixel::hsl(200.0, 1.0, 0.5),
+            )),
+            WallpaperGroup::P4g,
+            pt(100, 100),
+        ),
+        WallpaperTF::new(
+            Box::new(SolidTriangle::new_any(
+                pt(15, 5),
+                pt(40, 10),
+                pt(30, 20),
+                Pixel::hsl(330.0, 1.0, 0.6),
+            )),
+            WallpaperGroup::P6m,
+            pt(120, 120),
+        )
+    ]
+}
+
+pub fn test_kaleidoscope() -> Vec<Box<dyn Region>> {
+    make_regions![
+        ColorWaveTF::new(
+            curves::diagonal(0.0, 720.0),
+            curves::constant(0.8),
+            curves::linear_x(0.3, 0.7),
+        ),
+        KaleidoscopeTF::canvas(pt(300, 400), 12, Symmetry::Dihedral),
+        KaleidoscopeTF::new(
+            Box::new(SolidTriangle::new_any(
+                pt(380, 410),
+                pt(470, 430),
+                pt(420, 480),
+                Pixel::hsl(60.0, 1.0, 0.5),
+            )),
+            pt(300, 400),
+            8,
+            Symmetry::Cyclic,
+        )
+    ]
+}
+
+pub fn test_tileable() -> (Point, Vec<Box<dyn Region>>) {
+    let size = Hexagon::tileable_size(30, 9, 6);
+    let regions = make_regions![
+        ColorWaveTF::new(
+            curves::tileable(curves::diagonal(0.0, 240.0)),
+            curves::noise(0.6, 1.0, 4, 11),
+            curves::wave_y(0.4, 0.6, 2),
+        ),
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(Hexagon::tessellate_tileable(30, &size)),
+            vec![
+                Pixel::hsl(0.0, 1.0, 0.5),
+                Pixel::hsl(120.0, 1.0, 0.5),
+                Pixel::hsl(240.0, 1.0, 0.5),
+            ],
+        )
+    ];
+    (size, regions)
+}
+
+pub fn test_compound() -> Vec<Box<dyn Region>> {
+    let square = |x: i32, y: i32, size: i32| {
+        vec![
+            pt(x, y),
+            pt(x + size, y),
+            pt(x + size, y + size),
+            pt(x, y + size),
+        ]
+    };
+    let frames = RectanglePoly::tessellate(
+        &pt(300, 400),
+        &pt(60, 60),
+        &Rectangle::new(pt(0, 0), pt(600, 200)),
+        true,
+    )
+    .iter()
+    .map(|cell| RectanglePoly::frame(&cell.bounds(), 8))
+    .collect();
+    make_regions![
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(frames),
+            vec![Pixel::hsl(30.0, 1.0, 0.5), Pixel::hsl(200.0, 1.0, 0.5)],
+        ),
+        SolidColorPolygon::new(
+            // a letter A, with the hole in its bowl
+            Polygon::with_holes(
+                &[
+                    pt(100, 300),
+                    pt(140, 300),
+                    pt(155, 350),
+                    pt(225, 350),
+                    pt(240, 300),
+                    pt(280, 300),
+                    pt(210, 560),
+                    pt(170, 560),
+                ],
+                &[vec![pt(170, 390), pt(210, 390), pt(190, 480)]],
+            ),
+            Pixel::hsl(0.0, 0.0, 0.9),
+        ),
+        SolidColorPolygon::new(
+            Polygon::compound(
+                &[square(340, 300, 120), square(400, 360, 120)],
+                FillRule::EvenOdd,
+            ),
+            Pixel::hsl(120.0, 1.0, 0.5),
+        ),
+        SolidColorPolygon::new(
+            Polygon::compound(
+                &[square(340, 560, 120), square(400, 620, 120)],
+                FillRule::NonZero,
+            ),
+            Pixel::hsl(280.0, 1.0, 0.5),
+        )
+    ]
+}
+
+pub fn test_boolean() -> Vec<Box<dyn Region>> {
+    let octagon = |x: i32, y: i32, r: i32| {
+        let h = r * 5 / 12;
+        Polygon::from_vertices(&[
+            pt(x - h, y - r),
+            pt(x + h, y - r),
+            pt(x + r, y - h),
+            pt(x + r, y + h),
+            pt(x + h, y + r),
+            pt(x - h, y + r),
+            pt(x - r, y + h),
+            pt(x - r, y - h),
+        ])
+    };
+    let bounds = Rectangle::new(pt(50, 450), pt(500, 300));
+    let tiles = PolygonTessellation::new(Hexagon::tessellate(&pt(300, 600), 30, &bounds));
+    let window = octagon(300, 250, 150);
+    let clipped_to_window = PolygonTessellation::new(
+        Hexagon::tessellate(
+            &pt(300, 250),
+            20,
+            &Rectangle::new(pt(100, 50), pt(400, 400)),
+        )
+        .iter()
+        .map(|tile| tile.intersection(&window))
+        .filter(|tile| !tile.is_empty())
+        .collect(),
+    );
+    make_regions![
+        ColoredTessellationTF::new(
+            tiles.clip(&bounds),
+            vec![
+                Pixel::hsl(0.0, 1.0, 0.5),
+                Pixel::hsl(120.0, 1.0, 0.5),
+                Pixel::hsl(240.0, 1.0, 0.5),
+            ],
+        ),
+        ColoredTessellationTF::new(
+            clipped_to_window,
+            vec![Pixel::hsl(40.0, 1.0, 0.6), Pixel::hsl(200.0, 1.0, 0.4)],
+        ),
+        SolidColorPolygon::new(
+            octagon(120, 120, 60).difference(&octagon(160, 140, 40)),
+            Pixel::hsl(300.0, 1.0, 0.6),
+        ),
+        SolidColorPolygon::new(
+            octagon(470, 120, 50).xor(&octagon(510, 150, 50)),
+            Pixel::hsl(60.0, 1.0, 0.6),
+        )
+    ]
+}
+
+pub fn test_circles() -> Vec<Box<dyn Region>> {
+    make_regions![
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(Circle::tessellate(
+                &pt(300, 400),
+                12.0,
+                40,
+                &Rectangle::new(pt(0, 0), pt(600, 800)),
+            )),
+            vec![Pixel::hsl(50.0, 1.0, 0.6), Pixel::hsl(200.0, 1.0, 0.6)],
+        ),
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(Annulus::concentric(&pt(300, 550), 20.0, 8)),
+            vec![Pixel::hsl(0.0, 0.0, 0.1), Pixel::hsl(0.0, 0.0, 0.9)],
+        ),
+        SolidColorPolygon::new(
+            Ellipse::new(&pt(150, 180), 110.0, 50.0, 30.0),
+            Pixel::hsl(280.0, 1.0, 0.5),
+        ),
+        SolidColorPolygon::new(
+            Sector::new(&pt(450, 180), 100.0, 30.0, 300.0),
+            Pixel::hsl(60.0, 1.0, 0.5),
+        ),
+        SolidColorPolygon::new(
+            ArcBand::new(&pt(300, 550), 170.0, 200.0, 200.0, 140.0),
+            Pixel::hsl(120.0, 1.0, 0.4),
+        ),
+        SolidColorPolygon::new(
+            Hexagon::from_bounds(&Rectangle::new(pt(400, 300), pt(120, 120)))
+                .difference(&Circle::new(&pt(460, 360), 30.0)),
+            Pixel::hsl(20.0, 1.0, 0.5),
+        )
+    ]
+}
+
+pub fn test_stars() -> Vec<Box<dyn Region>> {
+    let polygons = (3..9)
+        .map(|sides| RegularPolygon::new(&pt(50 + (sides - 3) * 100, 700), 40.0, sides as u32, 0.0))
+        .collect();
+    let stars = (0..6)
+        .map(|i| Star::new(&pt(50 + i * 100, 560), 45.0, 20.0, 4 + i as u32, 0.0))
+        .collect();
+    make_regions![
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(polygons),
+            vec![Pixel::hsl(200.0, 1.0, 0.5)],
+        ),
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(stars),
+            vec![Pixel::hsl(50.0, 1.0, 0.5)],
+        ),
+        SolidColorPolygon::new(
+            Star::schlafli(&pt(150, 380), 100.0, 5, 2, 0.0, FillRule::NonZero),
+            Pixel::hsl(0.0, 0.0, 0.9),
+        ),
+        SolidColorPolygon::new(
+            Star::schlafli(&pt(450, 380), 100.0, 7, 3, 0.0, FillRule::EvenOdd),
+            Pixel::hsl(300.0, 1.0, 0.6),
+        ),
+        SolidColorPolygon::new(
+            Star::schlafli(&pt(150, 150), 100.0, 6, 2, 0.0, FillRule::EvenOdd),
+            Pixel::hsl(120.0, 1.0, 0.4),
+        ),
+        SolidColorPolygon::new(
+            RegularPolygon::from_bounds(&Rectangle::new(pt(350, 60), pt(200, 160)), 6, 0.0),
+            Pixel::hsl(30.0, 1.0, 0.5),
+        )
+    ]
+}
+
+pub fn test_strokes() -> Vec<Box<dyn Region>> {
+    let grid = Stroke::new(1.0);
+    let mut grid_lines: Vec<Polygon> = (1..6)
+        .map(|i| grid.line(&ptf(i as f32 * 100.0, 0.0), &ptf(i as f32 * 100.0, 800.0)))
+        .collect();
+    grid_lines.extend(
+        (1..8).map(|i| grid.line(&ptf(0.0, i as f32 * 100.0), &ptf(600.0, i as f32 * 100.0))),
+    );
+
+    let zigzag = |y: f32| {
+        [
+            ptf(60.0, y),
+            ptf(160.0, y + 60.0),
+            ptf(200.0, y),
+            ptf(240.0, y + 60.0),
+        ]
+    };
+    let styles = [
+        (LineCap::Butt, LineJoin::Miter),
+        (LineCap::Round, LineJoin::Round),
+        (LineCap::Square, LineJoin::Bevel),
+    ];
+    let polylines = styles
+        .iter()
+        .enumerate()
+        .map(|(i, (cap, join))| {
+            Stroke::new(16.0)
+                .cap(*cap)
+                .join(*join)
+                .polyline(&zigzag(640.0 - i as f32 * 110.0), false)
+        })
+        .collect();
+
+    let dashed = Stroke::new(6.0)
+        .cap(LineCap::Round)
+        .dashed(vec![20.0, 12.0], 0.0);
+    let spiral: Vec<PointFloat> = (0..120)
+        .map(|i| {
+            let angle = i as f32 * 0.15;
+            let radius = 10.0 + i as f32 * 0.9;
+            ptf(450.0 + radius * angle.cos(), 580.0 + radius * angle.sin())
+        })
+        .collect();
+
+    let hexagons =
+        Hexagon::tessellate(&pt(300, 150), 30, &Rectangle::new(pt(40, 20), pt(520, 240)));
+    let outline = Stroke::new(3.0).join(LineJoin::Round);
+    make_regions![
+        ColorWaveTF::new(
+            curves::radiate_linear(0.0, 360.0),
+            curves::constant(0.5),
+            curves::radiate(0.6, 0.5),
+        ),
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(grid_lines),
+            vec![Pixel::hsl(0.0, 0.0, 1.0)],
+        ),
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(polylines),
+            vec![Pixel::hsl(220.0, 1.0, 0.3)],
+        ),
+        SolidColorPolygon::new(dashed.polyline(&spiral, false), Pixel::hsl(0.0, 0.0, 0.1)),
+        ColoredTessellationTF::new(
+            PolygonTessellation::new(hexagons.iter().map(|hex| outline.outline(hex)).collect()),
+            vec![Pixel::hsl(0.0, 0.0, 0.1)],
+        )
+    ]
+}
+
+pub fn test_paths() -> Vec<Box<dyn Region>> {
+    let heart = Path::parse(
+        "M 300 520 C 300 560 250 620 180 620 C 80 620 80 500 80 500 \
+         C 80 440 140 380 300 260 C 460 380 520 440 520 500 \
+         C 520 500 520 620 420 620 C 350 620 300 560 300 520 Z",
+    )
+    .unwrap();
+    // a ring drawn as two arcs with a reversed inner sub path
+    let ring = Path::parse(
+        "M100,150 a60,60 0 1,0 120,0 a60,60 0 1,0 -120,0 z \
+         m30,0 a30,30 0 1,1 60,0 a30,30 0 1,1 -60,0 z",
+    )
+    .unwrap();
+    let wave = Path::parse("M260 100 q 30 80 60 0 t 60 0 t 60 0 t 60 0").unwrap();
+    let loops = Path::new()
+        .move_to(ptf(260.0, 200.0))
+        .cubic_to(ptf(420.0, 300.0), ptf(300.0, 20.0), ptf(520.0, 200.0))
+        .line_to(ptf(520.0, 240.0))
+        .arc_to(ptf(130.0, 40.0), 10.0, false, true, ptf(260.0, 240.0))
+        .close();
+    make_regions![
+        SolidColorPolygon::new(heart.fill(FillRule::NonZero), Pixel::hsl(350.0, 0.9, 0.55)),
+        SolidColorPolygon::new(
+            heart.stroke(&Stroke::new(6.0).join(LineJoin::Round)),
+            Pixel::hsl(350.0, 0.9, 0.25),
+        ),
+        SolidColorPolygon::new(ring.fill(FillRule::NonZero), Pixel::hsl(40.0, 1.0, 0.5)),
+        SolidColorPolygon::new(
+            wave.stroke(&Stroke::new(8.0).cap(LineCap::Round)),
+            Pixel::hsl(200.0, 1.0, 0.4),
+        ),
+        SolidColorPolygon::new(loops.fill(FillRule::EvenOdd), Pixel::hsl(120.0, 0.7, 0.5))
+    ]
+}
+
+pub fn test_transforms() -> Vec<Box<dyn Region>> {
+    let hexagons = PolygonTessellation::new(Hexagon::tessellate(
+        &pt(300, 600),
+        20,
+        &Rectangle::new(pt(150, 500), pt(300, 200)),
+    ))
+    .clip(&Rectangle::new(pt(180, 500), pt(240, 200)));
+    // the row based coloring does not follow a rotated grid, so color each
+    // hexagon by where it was before the rotation
+    let hex_colors = hexagons
+        .polygons
+        .iter()
+        .map(|hex| {
+            let center = hex.bounds().center();
+            Pixel::hsl(
+                (center.x - 180) as f32 * 1.5,
+                0.8,
+                0.3 + (center.y - 500) as f32 / 500.0,
+            )
+        })
+        .collect();
+    let hexagons = hexagons.transform(&Transform::rotate(30.0).around(&ptf(300.0, 600.0)));
+
+    // an 8 by 8 board, leaning over to the right
+    let squares = (0..64)
+        .map(|i| {
+            Polygon::from(Rectangle::new(
+                pt(60 + (i % 8) * 30, 80 + (i / 8) * 30),
+                pt(30, 30),
+            ))
+        })
+        .collect();
+    let board_colors = (0..64)
+        .map(|i| Pixel::hsl(0.0, 0.0, [0.1, 0.9][(i % 8 + i / 8) % 2]))
+        .collect();
+    let board = PolygonTessellation::new(squares)
+        .transform(&Transform::skew(25.0, 0.0).around(&ptf(180.0, 200.0)));
+
+    let shear = Transform::scale(1.0, 0.5).then(&Transform::skew(0.0, 20.0));
+    let band = Polygon::from_conic(
+        Conic::circle(ptf(460.0, 220.0), 90.0)
+            .hollow(0.5)
+            .wedge(0.0, 270.0)
+            .transform(&shear.around(&ptf(460.0, 220.0))),
+    );
+    let star = Star::new(&pt(460, 380), 60.0, 25.0, 5, 0.0)
+        .transform(&Transform::scale(1.6, 0.8).around(&ptf(460.0, 380.0)));
+    make_regions![
+        ColoredTessellationTF::descrete(hexagons, hex_colors),
+        ColoredTessellationTF::descrete(board, board_colors),
+        SolidColorPolygon::new(band, Pixel::hsl(40.0, 1.0, 0.5)),
+        SolidColorPolygon::new(star, Pixel::hsl(280.0, 0.8, 0.6))
+    ]
+}
+
+pub fn test_subpixel() -> Vec<Box<dyn Region>> {
+    // each triangle sits a quarter pixel further right and grows by a fifth of
+    // a pixel, so the steps between neighbours are too small to round
+    let triangles = (0..40)
+        .map(|i| {
+            let origin = ptf(20.0 + i as f32 * 13.25, 600.0);
+            let size = 6.0 + i as f32 * 0.2;
+            Polygon::float_triangle(
+                origin,
+                origin + ptf(size, size * 0.3),
+                origin + ptf(size * 0.4, size * 2.5),
+            )
+        })
+        .collect();
+    // a fan of thin slivers around one center, which only covers the disk
+    // without gaps when the shared split points are not rounded
+    let center = ptf(300.3, 300.7);
+    let fan = (0..90)
+        .map(|i| {
+            let angle = |step: i32| (step as f32 * 4.0).to_radians();
+            let corner = |step: i32| center + ptf(angle(step).cos(), angle(step).sin()) * 220.0;
+            Polygon::float_triangle(center, corner(i), corner(i + 1))
+        })
+        .collect();
+    make_regions![
+        ColoredTessellationTF::descrete(
+            PolygonTessellation::new(triangles),
+            (0..40)
+                .map(|i| Pixel::hsl(i as f32 * 9.0, 0.8, 0.5))
+                .collect(),
+        ),
+        ColoredTessellationTF::descrete(
+            PolygonTessellation::new(fan),
+            (0..90)
+                .map(|i| Pixel::hsl(i as f32 * 4.0, 0.8, 0.5))
+                .collect(),
+        )
+    ]
+}
+
+pub fn test_anti_alias() -> Vec<Box<dyn Region>> {
+    // the same rotated hexagons twice, aliased on the left and anti aliased
+    // on the right
+    let hexagons = |bounds: Rectangle| {
+        // the grid starts at the origin, so move it down and to the left
+        // until the rotated grid covers the whole top of the canvas
+        let grid = PolygonTessellation::new(Hexagon::tessellate(
+            &pt(300, 560),
+            24,
+            &Rectangle::new(pt(0, 0), pt(1000, 1000)),
+        ))
+        .transform(
+            &Transform::translate(-300.0, -100.0)
+                .then(&Transform::rotate(17.0).around(&ptf(300.0, 560.0))),
+        );
+        // clip each hexagon by itself, so that the colors stay the same on
+        // both sides
+        let (polygons, colors) = grid
+            .polygons
+            .iter()
+            .enumerate()
+            .map(|(i, hex)| {
+                (
+                    hex.clip(&bounds),
+                    Pixel::hsl((i * 47 % 360) as f32, 0.7, 0.5),
+                )
+            })
+            .filter(|(hex, _)| !hex.is_empty())
+            .unzip();
+        ColoredTessellationTF::descrete(PolygonTessellation::new(polygons), colors)
+    };
+    let star = Star::new(&pt(300, 170), 140.0, 60.0, 7, 10.0);
+    let slivers = (0..12)
+        .map(|i| {
+            let x = 40.0 + i as f32 * 45.0;
+            Polygon::float_triangle(ptf(x, 20.0), ptf(x + 3.0, 20.0), ptf(x + 30.0, 330.0))
+        })
+        .collect();
+    make_regions![
+        hexagons(Rectangle::new(pt(0, 340), pt(300, 460))),
+        hexagons(Rectangle::new(pt(300, 340), pt(300, 460))).anti_alias(AntiAlias::Supersample(4)),
+        SolidColorPolygon::new(star, Pixel::hsl(50.0, 0.9, 0.6))
+            .anti_alias(AntiAlias::Supersample(4)),
+        ColoredTessellationTF::descrete(
+            PolygonTessellation::new(slivers),
+            (0..12)
+                .map(|i| Pixel::hsl(i as f32 * 30.0, 0.8, 0.6))
+                .collect(),
+        )
+        .anti_alias(AntiAlias::Supersample(8))
+    ]
+}
+
+/// Returns the scale to supersample at along with the regions.
+pub fn test_supersample() -> (i32, Vec<Box<dyn Region>>) {
+    let scale = 3;
+    let hexagons = PolygonTessellation::new(Hexagon::tessellate(
+        &pt(300, 560),
+        24,
+        &Rectangle::new(pt(0, 0), pt(1000, 1000)),
+    ))
+    .transform(
+        &Transform::translate(-300.0, -100.0)
+            .then(&Transform::rotate(17.0).around(&ptf(300.0, 560.0))),
+    )
+    .clip(&Rectangle::new(pt(0, 340), pt(600, 460)));
+    let hex_colors = (0..hexagons.polygons.len())
+        .map(|i| Pixel::hsl((i * 47 % 360) as f32, 0.7, 0.5))
+        .collect();
+    let star = Star::new(&pt(300, 170), 140.0, 60.0, 7, 10.0);
+    let slivers = (0..12)
+        .map(|i| {
+            let x = 40.0 + i as f32 * 45.0;
+            Polygon::float_triangle(ptf(x, 20.0), ptf(x + 3.0, 20.0), ptf(x + 30.0, 330.0))
+        })
+        .collect();
+    (
+        scale,
+        make_regions![
+            ColorWaveTF::new(
+                curves::wave_x(0.0, 360.0, 12),
+                curves::constant(0.6),
+                curves::constant(0.5),
+            ),
+            ColoredTessellationTF::descrete(hexagons, hex_colors),
+            SolidColorPolygon::new(star, Pixel::hsl(50.0, 0.9, 0.6)),
+            ColoredTessellationTF::descrete(
+                PolygonTessellation::new(slivers),
+                (0..12)
+                    .map(|i| Pixel::hsl(i as f32 * 30.0, 0.8, 0.6))
+                    .collect(),
+            )
+        ],
+    )
+}
+
+pub fn test_shading() -> Vec<Box<dyn Region>> {
+    // the same red, green and blue corners mixed in each color space
+    let primaries = [
+        Pixel::rgb(255, 0, 0),
+        Pixel::rgb(0, 255, 0),
+        Pixel::rgb(0, 0, 255),
+    ];
+    let triangle = |x: f32, space: ColorSpace| {
+        ShadedTriangle::new(
+            [ptf(x, 580.0), ptf(x + 180.0, 580.0), ptf(x + 90.0, 780.0)],
+            primaries,
+        )
+        .color_space(space)
+    };
+    // a gradient mesh over a jittered grid, with the hue running along x and
+    // the lightness along y
+    let bounds = Rectangle::new(pt(20, 300), pt(560, 260));
+    let mesh_points: Vec<(Point, Pixel)> = Delaunay::jittered_grid_points(&bounds, 40, 3)
+        .into_iter()
+        .map(|point| {
+            let hue = (point.x - 20) as f32 / 560.0 * 300.0;
+            let lightness = 0.3 + (point.y - 300) as f32 / 260.0 * 0.4;
+            (point, Pixel::hsl(hue, 0.8, lightness))
+        })
+        .collect();
+    make_regions![
+        triangle(10.0, ColorSpace::Srgb),
+        triangle(210.0, ColorSpace::Linear),
+        triangle(410.0, ColorSpace::OkLab),
+        ShadedMesh::from_points(&mesh_points, ColorSpace::OkLab),
+        ColorWaveTF::new(
+            curves::radiate_linear(0.0, 360.0),
+            curves::constant(0.5),
+            curves::radiate(0.6, 0.5),
+        ),
+        LowPolyTF::new(PointSource::Edges(600), 7).shaded(ColorSpace::Linear)
+    ]
+}
+
+pub fn test_textures() -> Vec<Box<dyn Region>> {
+    // draw a small picture to use as the texture
+    let mut texture = Image::new(200, 200);
+    let picture: Vec<Box<dyn Region>> = make_regions![
+        ColorWaveTF::new(
+            curves::radiate_linear(0.0, 360.0),
+            curves::constant(0.7),
+            curves::radiate(0.6, 0.4),
+        ),
+        SolidColorPolygon::new(
+            Star::new(&pt(100, 100), 80.0, 35.0, 5, 90.0),
+            Pixel::hsl(50.0, 1.0, 0.6),
+        ),
+        SolidColorPolygon::new(Circle::new(&pt(100, 100), 20.0), Pixel::hsl(0.0, 0.0, 0.1))
+    ];
+    for region in picture.iter() {
+        texture.apply_region(region.as_ref());
+    }
+    let texture = Rc::new(texture);
+
+    // the same 12 by 11 pixel piece of the edge of the circle blown up,
+    // without and with filtering
+    let magnified = |x: f32, sampling: Sampling| {
+        TexturedPolygon::from_uvs(
+            &[
+                ptf(x, 540.0),
+                ptf(x + 260.0, 540.0),
+                ptf(x + 260.0, 780.0),
+                ptf(x, 780.0),
+            ],
+            &[
+                ptf(112.0, 94.0),
+                ptf(124.0, 94.0),
+                ptf(124.0, 105.0),
+                ptf(112.0, 105.0),
+            ],
+            texture.clone(),
+        )
+        .sampling(sampling)
+    };
+
+    // every hexagon shows a different part of the texture, turned by a
+    // multiple of 60 degrees and mirrored at random
+    // the grid starts at the origin, so move it past the edges before
+    // clipping
+    let hexagons = PolygonTessellation::new(Hexagon::tessellate(
+        &pt(300, 260),
+        45,
+        &Rectangle::new(pt(0, 0), pt(700, 620)),
+    ))
+    .transform(&Transform::translate(-50.0, -50.0))
+    .clip(&Rectangle::new(pt(0, 0), pt(600, 520)));
+    let mut rng = Rng::new(11);
+    let to_texture = hexagons
+        .polygons
+        .iter()
+        .map(|hex| {
+            let center = hex.bounds().center().to_float();
+            let mirror = if rng.chance(0.5) { -1.0 } else { 1.0 };
+            Transform::translate(-center.x, -center.y)
+                .then(&Transform::rotate(rng.index(6) as f32 * 60.0))
+                .then(&Transform::scale(mirror, 1.0))
+                .then(&Transform::translate(
+                    rng.range_f32(60.0, 140.0),
+                    rng.range_f32(60.0, 140.0),
+                ))
+        })
+        .collect();
+    make_regions![
+        magnified(20.0, Sampling::Nearest),
+        magnified(320.0, Sampling::Bilinear),
+        TexturedTessellationTF::new(hexagons, texture.clone(), to_texture, Sampling::Bilinear)
+    ]
+}
+
+pub fn test_sdf() -> Vec<Box<dyn Region>> {
+    // two circles melted into a bar, with a glow around them
+    let emblem = Sdf::circle(70.0)
+        .translate(-110.0, 0.0)
+        .smooth_union(Sdf::circle(70.0).translate(110.0, 0.0), 40.0)
+        .smooth_union(Sdf::rounded_rect(300.0, 50.0, 20.0), 40.0)
+        .subtract(Sdf::circle(30.0))
+        .translate(300.0, 600.0);
+    let glow = emblem.curve(|d| 0.08 + 0.5 * (-d.max(0.0) / 40.0).exp());
+    let outline = Sdf::star(110.0, 45.0, 5).onion(6.0).translate(150.0, 330.0);
+    // lighter towards the middle, like a bevelled gem
+    let gem = Sdf::hexagon(110.0).rotate(10.0).translate(440.0, 330.0);
+    let bevel = gem.curve(|d| 0.3 + 0.4 * (-d / 25.0).clamp(0.0, 1.0));
+    // a strip of rounded squares crossed out by a line
+    let strip = Sdf::rounded_rect(30.0, 30.0, 8.0)
+        .rotate(45.0)
+        .repeat(ptf(50.0, 50.0))
+        .translate(0.0, -15.0)
+        .intersect(Sdf::rect(560.0, 110.0).translate(300.0, 110.0))
+        .subtract(Sdf::segment(ptf(40.0, 60.0), ptf(560.0, 160.0), 12.0));
+    make_regions![
+        ColorWaveTF::new(curves::constant(220.0), curves::constant(0.6), glow),
+        SolidSdf::new(emblem, Pixel::hsl(45.0, 0.9, 0.6)),
+        SolidSdf::new(outline, Pixel::hsl(0.0, 0.0, 0.95)),
+        SolidSdf::new(gem, Pixel::hsl(150.0, 0.7, 0.5)).lightness(bevel),
+        SolidSdf::new(strip, Pixel::hsl(330.0, 0.8, 0.6))
+    ]
+}
+
+pub fn test_tile_channels() -> Vec<Box<dyn Region>> {
+    // hexagons over the top half, each with its own hue and bevelled towards
+    // its edges
+    let hexagons = PolygonTessellation::new(Hexagon::tessellate(
+        &pt(300, 560),
+        40,
+        &Rectangle::new(pt(0, 0), pt(1000, 1000)),
+    ))
+    .transform(&Transform::translate(-200.0, -100.0))
+    .clip(&Rectangle::new(pt(0, 400), pt(600, 400)));
+    let bevel: TileShaderFn = Box::new(|tile: &TilePoint| {
+        let depth = (tile.edge_distance / 12.0).min(1.0);
+        Pixel::hsl((tile.polygon * 47 % 360) as f32, 0.7, 0.25 + 0.35 * depth)
+    });
+    // offset bricks over the bottom half, shaded across each brick and
+    // separated by dark grout
+    let bricks = PolygonTessellation::new(RectanglePoly::tessellate(
+        &pt(300, 200),
+        &pt(120, 50),
+        &Rectangle::new(pt(0, 0), pt(800, 500)),
+        true,
+    ))
+    .transform(&Transform::translate(-60.0, -25.0))
+    .clip(&Rectangle::new(pt(0, 0), pt(600, 400)));
+    let gradient: TileShaderFn = Box::new(|tile: &TilePoint| match tile.edge_distance < 3.0 {
+        true => Pixel::hsl(30.0, 0.1, 0.15),
+        false => Pixel::hsl(10.0 + 20.0 * tile.local.x, 0.6, 0.3 + 0.25 * tile.local.y),
+    });
+    make_regions![
+        TileShaderTF::new(hexagons, bevel).anti_alias(AntiAlias::Supersample(4)),
+        TileShaderTF::new(bricks, gradient)
+    ]
+}
+
+pub fn test_borders() -> Vec<Box<dyn Region>> {
+    // a mosaic of hexagons over the top half, with dark grout showing
+    // through the gaps between them
+    let mosaic = PolygonTessellation::new(Hexagon::tessellate(
+        &pt(300, 560),
+        30,
+        &Rectangle::new(pt(0, 0), pt(1000, 1000)),
+    ))
+    .transform(&Transform::translate(-200.0, -100.0))
+    .clip(&Rectangle::new(pt(0, 420), pt(600, 380)));
+    let mosaic_colors = (0..mosaic.polygons.len())
+        .map(|i| Pixel::hsl((i * 47 % 360) as f32, 0.6, 0.55))
+        .collect();
+    // rotated squares with a light outline
+    let squares = PolygonTessellation::new(RectanglePoly::tessellate(
+        &pt(300, 200),
+        &pt(60, 60),
+        &Rectangle::new(pt(0, 0), pt(1000, 1000)),
+        false,
+    ))
+    .transform(
+        &Transform::translate(-200.0, -200.0)
+            .then(&Transform::rotate(30.0).around(&PointFloat::new(300.0, 200.0))),
+    )
+    .clip(&Rectangle::new(pt(0, 0), pt(600, 400)));
+    make_regions![
+        ColorWaveTF::new(
+            curves::constant(30.0),
+            curves::constant(0.1),
+            curves::constant(0.2),
+        ),
+        ColoredTessellationTF::descrete(mosaic, mosaic_colors)
+            .border(TileBorder::new().inset(0.12))
+            .anti_alias(AntiAlias::Supersample(4)),
+        ColoredTessellationTF::new(
+            squares,
+            vec![
+                Pixel::hsl(200.0, 0.6, 0.4),
+                Pixel::hsl(220.0, 0.6, 0.3),
+                Pixel::hsl(240.0, 0.6, 0.4),
+            ],
+        )
+        .border(TileBorder::new().line(4.0, Pixel::hsl(50.0, 0.9, 0.8)))
+        .anti_alias(AntiAlias::Supersample(4)),
+        SolidColorPolygon::new(
+            Star::new(&pt(300, 200), 150.0, 60.0, 5, 0.0),
+            Pixel::hsl(0.0, 0.8, 0.55),
+        )
+        .border(
+            TileBorder::new()
+                .line(8.0, Pixel::hsl(0.0, 0.0, 0.95))
+                .inset(0.05)
+        )
+        .anti_alias(AntiAlias::Supersample(4))
+    ]
+}
+
+pub fn test_sampled_tiles() -> Vec<Box<dyn Region>> {
+    let hexagons = |side: i32, bounds: Rectangle| {
+        PolygonTessellation::new(Hexagon::tessellate(
+            &pt(300, 400),
+            side,
+            &Rectangle::new(pt(0, 0), pt(1000, 1000)),
+        ))
+        .transform(&Transform::translate(-200.0, -100.0))
+        .clip(&bounds)
+    };
+    // a small picture, shown as a mosaic of hexagons over the whole canvas
+    let mut picture = Image::new(60, 80);
+    let regions: Vec<Box<dyn Region>> = make_regions![
+        ColorWaveTF::new(
+            curves::diagonal(180.0, 300.0),
+            curves::constant(0.6),
+            curves::constant(0.4),
+        ),
+        SolidColorPolygon::new(
+            Star::new(&pt(30, 40), 26.0, 11.0, 5, 0.0),
+            Pixel::hsl(50.0, 1.0, 0.6),
+        )
+    ];
+    for region in regions.iter() {
+        picture.apply_region(region.as_ref());
+    }
+    // bricks across the bottom, colored from a gradient
+    let bricks = PolygonTessellation::new(RectanglePoly::tessellate(
+        &pt(300, 100),
+        &pt(50, 25),
+        &Rectangle::new(pt(0, 0), pt(800, 400)),
+        true,
+    ))
+    .transform(&Transform::translate(-50.0, -25.0))
+    .clip(&Rectangle::new(pt(0, 0), pt(600, 180)));
+    let sunset = Gradient::new(vec![
+        (0.0, Pixel::hsl(260.0, 0.6, 0.25)),
+        (0.5, Pixel::hsl(330.0, 0.8, 0.55)),
+        (1.0, Pixel::hsl(45.0, 1.0, 0.6)),
+    ])
+    .color_space(ColorSpace::OkLab);
+    // bigger squares over the top, colored from the hexagons below them
+    let squares = PolygonTessellation::new(
+        (0..20)
+            .map(|i| RectanglePoly::new(pt(200 + i % 5 * 40, 640 + i / 5 * 40), pt(40, 40)))
+            .collect(),
+    );
+    make_regions![
+        ColorWaveTF::new(
+            curves::constant(0.0),
+            curves::constant(0.0),
+            curves::constant(0.1),
+        ),
+        SampledTessellationTF::from_image(
+            hexagons(10, Rectangle::new(pt(0, 0), pt(600, 800))),
+            Rc::new(picture),
+        )
+        .border(TileBorder::new().inset(0.15))
+        .anti_alias(AntiAlias::Supersample(4)),
+        SampledTessellationTF::from_curves(
+            hexagons(20, Rectangle::new(pt(0, 620), pt(600, 180))),
+            curves::linear_x(0.0, 300.0),
+            curves::constant(0.8),
+            curves::linear_y(0.3, 0.7),
+        )
+        .anti_alias(AntiAlias::Supersample(4)),
+        SampledTessellationTF::from_gradient(bricks, sunset, curves::linear_x(0.0, 1.0))
+            .border(TileBorder::new().line(1.0, Pixel::hsl(0.0, 0.0, 0.1))),
+        SampledTessellationTF::from_canvas(squares)
+            .border(TileBorder::new().line(2.0, Pixel::hsl(0.0, 0.0, 0.9)))
+    ]
+}
+
+pub fn test_averages() -> Vec<Box<dyn Region>> {
+    // a busy picture of stripes and small dots, pixelated with a different
+    // average in every quarter: the mean at the bottom left, the median at
+    // the bottom right, the mode at the top left and k-means at the top right
+    let mut rng = Rng::new(11);
+    let dots = PolygonTessellation::new(
+        (0..400)
+            .map(|_| Circle::new(&pt(rng.range(0, 600), rng.range(0, 800)), 3.0))
+            .collect(),
+    );
+    let quarter = |x: i32, y: i32, average: Average| {
+        let hexagons = PolygonTessellation::new(Hexagon::tessellate(
+            &pt(300, 400),
+            16,
+            &Rectangle::new(pt(0, 0), pt(1000, 1000)),
+        ))
+        .transform(&Transform::translate(-200.0, -100.0))
+        .clip(&Rectangle::new(pt(x, y), pt(300, 400)));
+        BlendedTessellationTF::new(hexagons).average(average)
+    };
+    make_regions![
+        ColorWaveTF::new(
+            curves::wave_x(200.0, 340.0, 12),
+            curves::constant(0.8),
+            curves::noise(0.25, 0.6, 12, 5),
+        ),
+        ColoredTessellationTF::new(dots, vec![Pixel::hsl(0.0, 0.0, 0.95)]),
+        quarter(0, 0, Average::Mean),
+        quarter(300, 0, Average::Median),
+        quarter(0, 400, Average::Mode),
+        quarter(300, 400, Average::KMeans(3))
+    ]
+}
+
+pub fn test_palette() -> Vec<Box<dyn Region>> {
+    let picture = || -> Vec<Box<dyn Region>> {
+        make_regions![
+            ColorWaveTF::new(
+                curves::radiate_linear(0.0, 360.0),
+                curves::constant(0.7),
+                curves::noise(0.3, 0.7, 6, 3),
+            ),
+            SolidColorPolygon::new(
+                Star::new(&pt(300, 440), 200.0, 90.0, 5, 0.0),
+                Pixel::hsl(50.0, 1.0, 0.6),
+            )
+            .border(TileBorder::new().line(10.0, Pixel::hsl(0.0, 0.0, 0.1)))
+            .anti_alias(AntiAlias::Supersample(4)),
+            SolidColorPolygon::new(
+                Circle::new(&pt(300, 440), 50.0),
+                Pixel::hsl(200.0, 0.9, 0.5)
+            )
+        ]
+    };
+    // pick the palettes out of the picture before it is drawn on the canvas
+    let mut image = Image::new(600, 800);
+    for region in picture().iter() {
+        image.apply_region(region.as_ref());
+    }
+    let median_cut = Quantizer::MedianCut.palette(&image, 8);
+    let octree = Quantizer::Octree.palette(&image, 8);
+    let kmeans = Quantizer::KMeans.palette(&image, 8);
+
+    // the picture remapped to each palette in a quarter above a strip of
+    // swatches: median cut at the bottom left, the octree at the bottom
+    // right, k-means at the top left and k-means with dithering at the top
+    // right
+    let quarter = |x: i32, y: i32| Rectangle::new(pt(x, y), pt(300, 360));
+    let mut regions = picture();
+    regions.extend(make_regions![
+        PaletteMapTF::new(median_cut.clone()).within(quarter(0, 80)),
+        PaletteMapTF::new(octree.clone()).within(quarter(300, 80)),
+        PaletteMapTF::new(kmeans.clone()).within(quarter(0, 440)),
+        PaletteMapTF::new(kmeans.clone())
+            .dither()
+            .within(quarter(300, 440))
+    ]);
+    regions.push(Box::new(SolidRectangle::new(
+        pt(0, 0),
+        pt(600, 80),
+        Pixel::hsl(0.0, 0.0, 0.1),
+    )));
+    let swatches = [median_cut, octree, kmeans];
+    for (row, palette) in swatches.iter().enumerate() {
+        for (i, color) in palette.iter().enumerate() {
+            regions.push(Box::new(SolidRectangle::new(
+                pt(i as i32 * 75, 54 - row as i32 * 27),
+                pt(75, 26),
+                *color,
+            )));
+        }
+    }
+    regions
+}
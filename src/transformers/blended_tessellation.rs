@@ -1,46 +1,58 @@
 use crate::{
     images::{Image, Pixel},
-    regions::{tess, Point, Region},
+    palette::Average,
+    regions::{tess, AntiAlias, Point, Region},
 };
 
+/// Fills every polygon of a tessellation with the average of the pixels
+/// below it, for a pixelated or mosaic version of the image.
 pub struct BlendedTessellationTF {
     tessellation: tess::PolygonTessellation,
+    anti_alias: AntiAlias,
+    average: Average,
 }
 
 impl BlendedTessellationTF {
     pub fn new(tessellation: tess::PolygonTessellation) -> Self {
-        Self { tessellation }
+        Self {
+            tessellation,
+            anti_alias: AntiAlias::None,
+            average: Average::Mean,
+        }
+    }
+
+    pub fn anti_alias(mut self, anti_alias: AntiAlias) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+
+    pub fn average(mut self, average: Average) -> Self {
+        self.average = average;
+        self
     }
 }
 
 impl Region for BlendedTessellationTF {
     fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
-        for polygon in self.tessellation.polygons.iter() {
+        for polygon in image.on_canvas(&self.tessellation.polygons).iter() {
             let mut pixels_to_blend = vec![];
             for point in polygon.iter_points() {
                 if image.contains(&point) {
-                    pixels_to_blend.push(image.get_pixel(&point));
+                    pixels_to_blend.push(*image.get_pixel(&point));
                 }
             }
 
             if pixels_to_blend.len() == 0 {
                 continue;
             }
+            let blended_pixel = self.average.of(&pixels_to_blend);
 
-            let mut alpha: u8 = (255 / pixels_to_blend.len()) as u8;
-            if alpha == 0 {
-                alpha = 1;
-            }
-            let alpha = alpha;
-
-            let mut blended_pixel = pixels_to_blend[0].clone().set_alpha(alpha);
-            for pixel_to_blind in pixels_to_blend.iter().skip(1) {
-                blended_pixel = blended_pixel.blend(&pixel_to_blind.clone().set_alpha(alpha));
-            }
-
-            for point in polygon.iter_points() {
+            for (point, coverage) in polygon.iter_coverage(self.anti_alias) {
                 if image.contains(&point) {
-                    mutations.push((point, blended_pixel.clone()));
+                    mutations.push((
+                        point.with_coverage(coverage),
+                        blended_pixel.with_coverage(coverage),
+                    ));
                 }
             }
         }
@@ -1,12 +1,15 @@
+use super::tile::{self, TileBorder};
 use crate::{
     images::{Image, Pixel},
-    regions::{tess, Point, PointAnnotation, Region},
+    regions::{tess, AntiAlias, Point, Region},
 };
 
 pub struct ColoredTessellationTF {
     tessellation: tess::PolygonTessellation,
     colors: Vec<Pixel>,
     use_descrete_colors: bool,
+    anti_alias: AntiAlias,
+    border: TileBorder,
 }
 
 impl ColoredTessellationTF {
@@ -15,6 +18,8 @@ impl ColoredTessellationTF {
             tessellation,
             colors,
             use_descrete_colors: false,
+            anti_alias: AntiAlias::None,
+            border: TileBorder::new(),
         }
     }
     pub fn descrete(tessellation: tess::PolygonTessellation, colors: Vec<Pixel>) -> Self {
@@ -22,23 +27,36 @@ impl ColoredTessellationTF {
             tessellation,
             colors,
             use_descrete_colors: true,
+            anti_alias: AntiAlias::None,
+            border: TileBorder::new(),
         }
     }
-}
 
-impl Region for ColoredTessellationTF {
-    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
-        if self.tessellation.polygons.len() == 0 {
-            return;
-        }
-        let full_bound_color = Pixel::hsl(120.0, 1.0, 0.5); // green
-        let standard_bound_color = Pixel::hsl(240.0, 1.0, 0.5); // blue
+    pub fn anti_alias(mut self, anti_alias: AntiAlias) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+
+    pub fn border(mut self, border: TileBorder) -> Self {
+        self.border = border;
+        self
+    }
 
+    /// The color of every polygon. Discrete colors follow the polygons one
+    /// to one, otherwise the colors cycle along each row, starting from the
+    /// other end of the list on every other row.
+    fn tile_colors(&self) -> Vec<Pixel> {
+        if self.use_descrete_colors {
+            return (0..self.tessellation.polygons.len())
+                .map(|index| self.colors[index % self.colors.len()])
+                .collect();
+        }
+        let mut result = vec![];
         let mut last_y = self.tessellation.polygons[0].bounds().origin.y;
         let mut color_index = 0;
         let mut last_start = 0;
 
-        for (index, poly) in self.tessellation.polygons.iter().enumerate() {
+        for poly in self.tessellation.polygons.iter() {
             if last_y != poly.bounds().origin.y {
                 if last_start == 0 {
                     color_index = self.colors.len() - 1;
@@ -49,27 +67,25 @@ impl Region for ColoredTessellationTF {
                 }
                 last_y = poly.bounds().origin.y;
             }
-            for point in poly.iter_points() {
-                if point.x < 0 || point.x >= image.size.x || point.y < 0 || point.y >= image.size.y
-                {
-                    continue;
-                }
-                if self.use_descrete_colors {
-                    let color = match point.annotation {
-                        PointAnnotation::Regular => self.colors[index].clone(),
-                        PointAnnotation::StandardEdge => self.colors[index].clone().hue_shift(0.0),
-                        PointAnnotation::FullEdge => self.colors[index].clone().hue_shift(0.0),
-                    };
-                    match point.annotation {
-                        PointAnnotation::Regular => mutations.push((point, color)),
-                        PointAnnotation::StandardEdge => mutations.push((point, color)),
-                        PointAnnotation::FullEdge => mutations.push((point, color)),
-                    }
-                } else {
-                    mutations.push((point, self.colors[color_index].clone()));
-                }
-            }
+            result.push(self.colors[color_index]);
             color_index = (color_index + 1) % self.colors.len();
         }
+        result
+    }
+}
+
+impl Region for ColoredTessellationTF {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        if self.tessellation.polygons.len() == 0 {
+            return;
+        }
+        tile::fill_tiles(
+            &self.tessellation.polygons,
+            &self.tile_colors(),
+            self.anti_alias,
+            &self.border,
+            image,
+            mutations,
+        );
     }
 }
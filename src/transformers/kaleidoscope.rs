@@ -0,0 +1,109 @@
+use std::f32::consts::TAU;
+
+use crate::{
+    images::{Image, Pixel},
+    regions::{Point, PointFloat, Region},
+};
+
+use super::motif::Motif;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Symmetry {
+    /// The wedge is rotated around the center.
+    Cyclic,
+    /// The wedge is mirrored into its neighbors, so each half wedge is the
+    /// reflection of the one next to it.
+    Dihedral,
+}
+
+pub enum KaleidoscopeSource {
+    Region(Box<dyn Region>),
+    Canvas,
+}
+
+/// Repeats the wedge that starts at `start_angle` (in degrees) N times
+/// around the center. For cyclic symmetry the wedge spans 360 / N degrees,
+/// for dihedral symmetry it spans half of that.
+pub struct KaleidoscopeTF {
+    source: KaleidoscopeSource,
+    center: Point,
+    folds: u32,
+    symmetry: Symmetry,
+    start_angle: f32,
+}
+
+impl KaleidoscopeTF {
+    pub fn new(region: Box<dyn Region>, center: Point, folds: u32, symmetry: Symmetry) -> Self {
+        Self {
+            source: KaleidoscopeSource::Region(region),
+            center,
+            folds,
+            symmetry,
+            start_angle: 0.0,
+        }
+    }
+
+    pub fn canvas(center: Point, folds: u32, symmetry: Symmetry) -> Self {
+        Self {
+            source: KaleidoscopeSource::Canvas,
+            center,
+            folds,
+            symmetry,
+            start_angle: 0.0,
+        }
+    }
+
+    pub fn rotate(mut self, start_angle: f32) -> Self {
+        self.start_angle = start_angle;
+        self
+    }
+
+    /// The point of the canvas that is reflected onto the given one, with
+    /// the center scaled from the output onto the canvas.
+    fn source_point(&self, x: f32, y: f32, scale: f32) -> (f32, f32) {
+        let center = PointFloat::pixel_center(&self.center) * scale;
+        let (cx, cy) = (center.x, center.y);
+        let (dx, dy) = (x - cx, y - cy);
+        let radius = (dx * dx + dy * dy).sqrt();
+        let start = self.start_angle.to_radians();
+        let wedge = TAU / self.folds.max(1) as f32;
+
+        let mut angle = (dy.atan2(dx) - start).rem_euclid(wedge);
+        if let Symmetry::Dihedral = self.symmetry {
+            if angle > wedge / 2.0 {
+                angle = wedge - angle;
+            }
+        }
+        let angle = angle + start;
+        (cx + radius * angle.cos(), cy + radius * angle.sin())
+    }
+}
+
+impl Region for KaleidoscopeTF {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        let motif = match &self.source {
+            KaleidoscopeSource::Region(region) => Some(Motif::render(region.as_ref(), image)),
+            KaleidoscopeSource::Canvas => None,
+        };
+
+        for y in 0..image.size.y {
+            for x in 0..image.size.x {
+                let center = PointFloat::pixel_center(&Point::new(x, y));
+                let (sx, sy) = self.source_point(center.x, center.y, image.scale());
+                let pixel = match &motif {
+                    Some(motif) => motif.get(sx, sy),
+                    None => {
+                        let source = Point::new(sx.floor() as i32, sy.floor() as i32);
+                        match image.contains(&source) {
+                            true => Some(*image.get_pixel(&source)),
+                            false => None,
+                        }
+                    }
+                };
+                if let Some(pixel) = pixel {
+                    mutations.push((Point::new(x, y), pixel));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    images::{ColorSpace, Image, Pixel},
+    polygons::Delaunay,
+    regions::{tess, Point, Rectangle, Region},
+};
+
+use super::{BlendedTessellationTF, ShadedMesh};
+
+pub enum PointSource {
+    Random(usize),
+    Grid(i32),
+    Sampled(usize),
+    Edges(usize),
+}
+
+pub struct LowPolyTF {
+    source: PointSource,
+    seed: u64,
+    shading: Option<ColorSpace>,
+}
+
+impl LowPolyTF {
+    pub fn new(source: PointSource, seed: u64) -> Self {
+        Self {
+            source,
+            seed,
+            shading: None,
+        }
+    }
+
+    /// Instead of filling every triangle with its average color, takes the
+    /// color of the image at each vertex and blends between them.
+    pub fn shaded(mut self, space: ColorSpace) -> Self {
+        self.shading = Some(space);
+        self
+    }
+
+    fn points(&self, image: &Image) -> Vec<Point> {
+        let bounds = Rectangle::normal(Point::new(image.size.x - 1, image.size.y - 1));
+        match self.source {
+            PointSource::Random(count) => Delaunay::random_points(&bounds, count, self.seed),
+            PointSource::Grid(spacing) => {
+                // the spacing is in pixels of the output, not of the canvas
+                Delaunay::jittered_grid_points(&bounds, spacing * image.scale() as i32, self.seed)
+            }
+            PointSource::Sampled(count) => Delaunay::sampled_points(image, count, self.seed),
+            PointSource::Edges(count) => Delaunay::edge_points(image, count, self.seed),
+        }
+    }
+
+    /// Moves points on the last row or column of pixels onto the far edge of
+    /// the canvas. Triangles only cover the pixels whose centers they contain,
+    /// so without this the last row and column would stay empty.
+    fn to_canvas_edge(point: &Point, image: &Image) -> Point {
+        let stretch = |value: i32, size: i32| match value == size - 1 {
+            true => size,
+            false => value,
+        };
+        Point::new(
+            stretch(point.x, image.size.x),
+            stretch(point.y, image.size.y),
+        )
+    }
+}
+
+impl Region for LowPolyTF {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        let points = self.points(image);
+        if let Some(space) = self.shading {
+            let colored: Vec<(Point, Pixel)> = points
+                .iter()
+                .map(|point| (Self::to_canvas_edge(point, image), *image.get_pixel(point)))
+                .collect();
+            return ShadedMesh::from_points(&colored, space).get_mutations(image, mutations);
+        }
+        let points: Vec<Point> = points
+            .iter()
+            .map(|point| Self::to_canvas_edge(point, image))
+            .collect();
+        let polygons = Delaunay::tessellate(&points);
+        BlendedTessellationTF::new(tess::PolygonTessellation::new(polygons))
+            .get_mutations(image, mutations);
+    }
+}
@@ -1,11 +1,32 @@
 mod blended_tessellation;
 mod colored_tessellation;
 mod gradient;
+mod kaleidoscope;
+mod low_poly;
+mod motif;
+mod palette_map;
+mod sampled_tessellation;
+mod shaded_triangle;
 mod solid_color;
+mod solid_sdf;
 mod solid_triangle;
+mod texture;
+mod tile;
+mod tile_shader;
+mod wallpaper;
 
 pub use blended_tessellation::BlendedTessellationTF;
 pub use colored_tessellation::ColoredTessellationTF;
 pub use gradient::ColorWaveTF;
+pub use kaleidoscope::{KaleidoscopeTF, Symmetry};
+pub use low_poly::{LowPolyTF, PointSource};
+pub use palette_map::PaletteMapTF;
+pub use sampled_tessellation::SampledTessellationTF;
+pub use shaded_triangle::{ShadedMesh, ShadedTriangle};
 pub use solid_color::SolidColorPolygon;
+pub use solid_sdf::SolidSdf;
 pub use solid_triangle::{SolidRectangle, SolidTriangle};
+pub use texture::{TexturedPolygon, TexturedTessellationTF};
+pub use tile::TileBorder;
+pub use tile_shader::{TileShaderFn, TileShaderTF};
+pub use wallpaper::{WallpaperGroup, WallpaperTF};
@@ -0,0 +1,52 @@
+use crate::{
+    images::{Image, Pixel},
+    regions::{Point, Rectangle, Region},
+};
+
+/// A region rendered once into a dense buffer so that it can be sampled
+/// many times by the symmetry transformers.
+pub struct Motif {
+    bounds: Rectangle,
+    pixels: Vec<Option<Pixel>>,
+}
+
+impl Motif {
+    pub fn render(region: &dyn Region, image: &Image) -> Self {
+        let sampled = image.sample_region(region);
+        if sampled.is_empty() {
+            return Self {
+                bounds: Rectangle::normal(Point::new(0, 0)),
+                pixels: vec![],
+            };
+        }
+        let min_x = sampled.keys().map(|p| p.x).min().unwrap();
+        let min_y = sampled.keys().map(|p| p.y).min().unwrap();
+        let max_x = sampled.keys().map(|p| p.x).max().unwrap();
+        let max_y = sampled.keys().map(|p| p.y).max().unwrap();
+        let bounds =
+            Rectangle::from_bounds(Point::new(min_x, min_y), Point::new(max_x + 1, max_y + 1));
+
+        let mut pixels = vec![None; (bounds.size.x * bounds.size.y) as usize];
+        for (point, pixel) in sampled {
+            let local = Point::new(point.x - min_x, point.y - min_y);
+            pixels[local.to_linear(bounds.size.x) as usize] = Some(pixel);
+        }
+        Self { bounds, pixels }
+    }
+
+    pub fn bounds(&self) -> Rectangle {
+        self.bounds.clone()
+    }
+
+    pub fn get(&self, x: f32, y: f32) -> Option<Pixel> {
+        let point = Point::new(x.floor() as i32, y.floor() as i32);
+        if !self.bounds.contains(&point) {
+            return None;
+        }
+        let local = Point::new(
+            point.x - self.bounds.origin.x,
+            point.y - self.bounds.origin.y,
+        );
+        self.pixels[local.to_linear(self.bounds.size.x) as usize]
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    images::{ColorSpace, Image, Pixel},
+    palette,
+    regions::{Point, Rectangle, Region},
+};
+
+/// Redraws the canvas with only the colors of a palette, replacing every
+/// pixel by the closest color, for limited palette prints.
+pub struct PaletteMapTF {
+    palette: Vec<Pixel>,
+    space: ColorSpace,
+    dither: bool,
+    bounds: Option<Rectangle>,
+}
+
+impl PaletteMapTF {
+    pub fn new(palette: Vec<Pixel>) -> Self {
+        Self {
+            palette,
+            space: ColorSpace::OkLab,
+            dither: false,
+            bounds: None,
+        }
+    }
+
+    /// The space the distance between colors is measured in.
+    pub fn color_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Spreads the difference between every pixel and its palette color
+    /// over the pixels next to it (Floyd-Steinberg dithering), so that
+    /// gradients are kept as patterns of palette colors instead of bands.
+    pub fn dither(mut self) -> Self {
+        self.dither = true;
+        self
+    }
+
+    /// Only redraws the part of the canvas inside the bounds.
+    pub fn within(mut self, bounds: Rectangle) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+}
+
+impl Region for PaletteMapTF {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        if self.palette.is_empty() {
+            return;
+        }
+        let encoded: Vec<[f32; 3]> = self
+            .palette
+            .iter()
+            .map(|color| self.space.encode(color))
+            .collect();
+        let bounds = match &self.bounds {
+            Some(bounds) => bounds.scale(image.scale() as i32),
+            None => Rectangle::normal(image.size.clone()),
+        };
+        let (width, height) = (bounds.size.x.max(0), bounds.size.y.max(0));
+        let mut errors = vec![[0.0; 3]; (width * height) as usize];
+        // rows run from the top of the picture down, like the eye reads it
+        for row in (0..height).rev() {
+            for column in 0..width {
+                let point = Point::new(bounds.origin.x + column, bounds.origin.y + row);
+                if !image.contains(&point) {
+                    continue;
+                }
+                let pixel = image.get_pixel(&point);
+                if pixel.a == 0 {
+                    continue;
+                }
+                let mut value = self.space.encode(pixel);
+                let error = errors[(row * width + column) as usize];
+                for (channel, error) in value.iter_mut().zip(error) {
+                    *channel += error;
+                }
+                let index = palette::nearest(&encoded, &value);
+                mutations.push((point, self.palette[index]));
+                if !self.dither {
+                    continue;
+                }
+                let difference: Vec<f32> = value
+                    .iter()
+                    .zip(encoded[index])
+                    .map(|(v, p)| v - p)
+                    .collect();
+                for (dx, dy, share) in [
+                    (1, 0, 7.0 / 16.0),
+                    (-1, -1, 3.0 / 16.0),
+                    (0, -1, 5.0 / 16.0),
+                    (1, -1, 1.0 / 16.0),
+                ] {
+                    let (x, y) = (column + dx, row + dy);
+                    if x < 0 || x >= width || y < 0 {
+                        continue;
+                    }
+                    let target = &mut errors[(y * width + x) as usize];
+                    for (channel, difference) in target.iter_mut().zip(difference.iter()) {
+                        *channel += difference * share;
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::rc::Rc;
+
+use super::tile::{self, TileBorder};
+use crate::{
+    curves::CurveFn,
+    images::{Gradient, Image, Pixel, Sampling},
+    regions::{tess, AntiAlias, Point, PointFloat, Region},
+};
+
+/// Where the color of a tile is read from.
+enum TileSource {
+    Curves {
+        hue_fn: CurveFn,
+        sat_fn: CurveFn,
+        lum_fn: CurveFn,
+    },
+    Gradient {
+        gradient: Gradient,
+        position_fn: CurveFn,
+    },
+    /// An image stretched over the canvas.
+    Image(Rc<Image>),
+    /// The canvas as it is before the tiles are drawn.
+    Canvas,
+}
+
+/// Fills every polygon of a tessellation with a single color, read at the
+/// center of its bounds, like a pixelated version of what the colors come
+/// from.
+pub struct SampledTessellationTF {
+    tessellation: tess::PolygonTessellation,
+    source: TileSource,
+    sampling: Sampling,
+    anti_alias: AntiAlias,
+    border: TileBorder,
+}
+
+impl SampledTessellationTF {
+    fn new(tessellation: tess::PolygonTessellation, source: TileSource) -> Self {
+        Self {
+            tessellation,
+            source,
+            sampling: Sampling::Nearest,
+            anti_alias: AntiAlias::None,
+            border: TileBorder::new(),
+        }
+    }
+
+    /// Colors every tile like `ColorWaveTF` would color its center.
+    pub fn from_curves(
+        tessellation: tess::PolygonTessellation,
+        hue_fn: CurveFn,
+        sat_fn: CurveFn,
+        lum_fn: CurveFn,
+    ) -> Self {
+        let source = TileSource::Curves {
+            hue_fn,
+            sat_fn,
+            lum_fn,
+        };
+        Self::new(tessellation, source)
+    }
+
+    /// Colors every tile from the gradient, at the position the curve gives
+    /// for its center.
+    pub fn from_gradient(
+        tessellation: tess::PolygonTessellation,
+        gradient: Gradient,
+        position_fn: CurveFn,
+    ) -> Self {
+        let source = TileSource::Gradient {
+            gradient,
+            position_fn,
+        };
+        Self::new(tessellation, source)
+    }
+
+    /// Colors every tile from the image, scaled to cover the whole canvas,
+    /// for a photo mosaic.
+    pub fn from_image(tessellation: tess::PolygonTessellation, image: Rc<Image>) -> Self {
+        Self::new(tessellation, TileSource::Image(image))
+    }
+
+    /// Colors every tile from what is already drawn below its center.
+    pub fn from_canvas(tessellation: tess::PolygonTessellation) -> Self {
+        Self::new(tessellation, TileSource::Canvas)
+    }
+
+    /// How images are read at the centers of the tiles.
+    pub fn sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn anti_alias(mut self, anti_alias: AntiAlias) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+
+    pub fn border(mut self, border: TileBorder) -> Self {
+        self.border = border;
+        self
+    }
+
+    fn sample(&self, image: &Image, center: &PointFloat) -> Pixel {
+        match &self.source {
+            TileSource::Curves {
+                hue_fn,
+                sat_fn,
+                lum_fn,
+            } => {
+                let point = center.truncate();
+                Pixel::hsl(
+                    hue_fn(&point, &image.size),
+                    sat_fn(&point, &image.size),
+                    lum_fn(&point, &image.size),
+                )
+            }
+            TileSource::Gradient {
+                gradient,
+                position_fn,
+            } => gradient.at(position_fn(&center.truncate(), &image.size)),
+            TileSource::Image(source) => {
+                let scaled = PointFloat::new(
+                    center.x * source.size.x as f32 / image.size.x as f32,
+                    center.y * source.size.y as f32 / image.size.y as f32,
+                );
+                source.sample(&scaled, self.sampling)
+            }
+            TileSource::Canvas => image.sample(center, self.sampling),
+        }
+    }
+}
+
+impl Region for SampledTessellationTF {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        let colors: Vec<Pixel> = self
+            .tessellation
+            .polygons
+            .iter()
+            .map(|polygon| {
+                let bounds = polygon.bounds();
+                let center = bounds.origin.to_float()
+                    + PointFloat::new(bounds.size.x as f32, bounds.size.y as f32) / 2.0;
+                self.sample(image, &image.to_canvas().apply(&center))
+            })
+            .collect();
+        tile::fill_tiles(
+            &self.tessellation.polygons,
+            &colors,
+            self.anti_alias,
+            &self.border,
+            image,
+            mutations,
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use crate::{
+    images::{ColorSpace, Image, Pixel},
+    polygons::Delaunay,
+    regions::{Point, PointFloat, Region, Triangle},
+};
+
+/// A triangle with a color at every vertex, blended smoothly across its face
+/// (Gouraud shading).
+pub struct ShadedTriangle {
+    triangle: Triangle,
+    colors: [Pixel; 3],
+    space: ColorSpace,
+}
+
+impl ShadedTriangle {
+    pub fn new(vertices: [PointFloat; 3], colors: [Pixel; 3]) -> Self {
+        let triangle = Triangle::new_float(vertices[0], vertices[1], vertices[2]);
+        // the triangle keeps its vertices counter clockwise, so the colors
+        // have to be swapped along with the vertices
+        let [c1, c2, c3] = colors;
+        let colors = match triangle.vertices()[1] == vertices[1] {
+            true => colors,
+            false => [c1, c3, c2],
+        };
+        Self {
+            triangle,
+            colors,
+            space: ColorSpace::Srgb,
+        }
+    }
+
+    pub fn color_space(mut self, space: ColorSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    fn shade(&self, triangle: &Triangle, encoded: &[[f32; 3]; 3], point: &Point) -> Pixel {
+        let weights = triangle
+            .barycentric(&PointFloat::pixel_center(point))
+            .map(|weight| weight.clamp(0.0, 1.0));
+        let colors = encoded.iter().zip(&self.colors).map(|(v, c)| (*v, c.a));
+        Pixel::mix_encoded(colors, &weights, self.space)
+    }
+}
+
+impl Region for ShadedTriangle {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        // scaling onto the canvas keeps the order of the vertices, so the
+        // colors stay with their vertices
+        let to_canvas = image.to_canvas();
+        let [p1, p2, p3] = self
+            .triangle
+            .vertices()
+            .map(|vertex| to_canvas.apply(&vertex));
+        let triangle = Triangle::new_float(p1, p2, p3);
+        let encoded = self.colors.map(|color| self.space.encode(&color));
+        for point in triangle.iter_points() {
+            if image.contains(&point) {
+                let color = self.shade(&triangle, &encoded, &point);
+                mutations.push((point, color));
+            }
+        }
+    }
+}
+
+/// A mesh of shaded triangles, such as a gradient mesh. Triangles that share
+/// an edge blend into each other without a seam, because both take the
+/// colors along the edge from the same two vertices.
+pub struct ShadedMesh {
+    triangles: Vec<ShadedTriangle>,
+}
+
+impl ShadedMesh {
+    pub fn new(triangles: Vec<ShadedTriangle>) -> Self {
+        Self { triangles }
+    }
+
+    /// Triangulates the points and shades every triangle between the colors
+    /// of its corners.
+    pub fn from_points(points: &[(Point, Pixel)], space: ColorSpace) -> Self {
+        let colors: HashMap<Point, Pixel> = points
+            .iter()
+            .map(|(point, color)| (Point::new(point.x, point.y), *color))
+            .collect();
+        let vertices: Vec<Point> = points
+            .iter()
+            .map(|(point, _)| Point::new(point.x, point.y))
+            .collect();
+        let triangles = Delaunay::triangulate(&vertices)
+            .into_iter()
+            .map(|corners| {
+                ShadedTriangle::new(
+                    corners.clone().map(|corner| corner.to_float()),
+                    corners.map(|corner| colors[&corner]),
+                )
+                .color_space(space)
+            })
+            .collect();
+        Self { triangles }
+    }
+}
+
+impl Region for ShadedMesh {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        for triangle in self.triangles.iter() {
+            triangle.get_mutations(image, mutations);
+        }
+    }
+}
@@ -1,31 +1,46 @@
+use super::tile::{self, TileBorder};
 use crate::{
     images::{Image, Pixel},
-    regions::{Point, PointAnnotation, Polygon, Region},
+    regions::{AntiAlias, Point, Polygon, Region},
 };
 
 pub struct SolidColorPolygon {
     polygon: Polygon,
     color: Pixel,
+    anti_alias: AntiAlias,
+    border: TileBorder,
 }
 
 impl SolidColorPolygon {
     pub fn new(polygon: Polygon, color: Pixel) -> Self {
-        Self { polygon, color }
+        Self {
+            polygon,
+            color,
+            anti_alias: AntiAlias::None,
+            border: TileBorder::new(),
+        }
+    }
+
+    pub fn anti_alias(mut self, anti_alias: AntiAlias) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+
+    pub fn border(mut self, border: TileBorder) -> Self {
+        self.border = border;
+        self
     }
 }
 
 impl Region for SolidColorPolygon {
     fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
-        let full_bound_color = Pixel::hsl(0.0, 1.0, 0.0);
-        let standard_bound_color = Pixel::hsl(180.0, 1.0, 1.0);
-        for point in self.polygon.iter_points() {
-            println!("Found pixel with annotation: {:?}", point.annotation);
-            let color = match point.annotation {
-                PointAnnotation::Regular => self.color.clone(),
-                PointAnnotation::FullEdge => full_bound_color.clone(),
-                PointAnnotation::StandardEdge => standard_bound_color.clone(),
-            };
-            mutations.push((point, color));
-        }
+        tile::fill_tiles(
+            std::slice::from_ref(&self.polygon),
+            &[self.color],
+            self.anti_alias,
+            &self.border,
+            image,
+            mutations,
+        );
     }
 }
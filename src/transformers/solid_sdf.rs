@@ -0,0 +1,58 @@
+use crate::{
+    curves::CurveFn,
+    images::{Image, Pixel},
+    regions::{Point, PointFloat, Region, Sdf},
+};
+
+/// Fills a signed distance shape with one color. Pixels within half a pixel
+/// of the edge are covered by how far inside of the edge their center lies,
+/// which smooths the edges.
+pub struct SolidSdf {
+    sdf: Sdf,
+    color: Pixel,
+    lum_fn: Option<CurveFn>,
+}
+
+impl SolidSdf {
+    pub fn new(sdf: Sdf, color: Pixel) -> Self {
+        Self {
+            sdf,
+            color,
+            lum_fn: None,
+        }
+    }
+
+    /// Varies the lightness of the color over the shape, for example with
+    /// `Sdf::curve` to shade it by the distance to its edge.
+    pub fn lightness(mut self, lum_fn: CurveFn) -> Self {
+        self.lum_fn = Some(lum_fn);
+        self
+    }
+}
+
+impl Region for SolidSdf {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        // the shape is laid out in pixels of the output, while the coverage
+        // is measured in pixels of the canvas
+        let scale = image.scale();
+        let output_size = Point::new(image.size.x / scale as i32, image.size.y / scale as i32);
+        for y in 0..image.size.y {
+            for x in 0..image.size.x {
+                let point = Point::new(x, y);
+                let center = PointFloat::pixel_center(&point) / scale;
+                let distance = self.sdf.distance(&center) * scale;
+                let coverage = (0.5 - distance).clamp(0.0, 1.0);
+                if coverage <= 0.0 {
+                    continue;
+                }
+                let color = match &self.lum_fn {
+                    Some(lum_fn) => self
+                        .color
+                        .luminate(lum_fn(&center.truncate(), &output_size)),
+                    None => self.color,
+                };
+                mutations.push((point.with_coverage(coverage), color.with_coverage(coverage)));
+            }
+        }
+    }
+}
@@ -25,8 +25,13 @@ impl SolidTriangle {
 }
 
 impl Region for SolidTriangle {
-    fn get_mutations(&self, _image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
-        for point in self.triangle.iter_points() {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        let to_canvas = image.to_canvas();
+        let [p1, p2, p3] = self
+            .triangle
+            .vertices()
+            .map(|vertex| to_canvas.apply(&vertex));
+        for point in Triangle::new_float(p1, p2, p3).iter_points() {
             mutations.push((point, self.color.clone()))
         }
     }
@@ -47,8 +52,8 @@ impl SolidRectangle {
 }
 
 impl Region for SolidRectangle {
-    fn get_mutations(&self, _image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
-        for point in self.rectangle.iter_points() {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        for point in self.rectangle.scale(image.scale() as i32).iter_points() {
             mutations.push((point, self.color.clone()))
         }
     }
@@ -0,0 +1,151 @@
+use std::rc::Rc;
+
+use crate::{
+    images::{Image, Pixel, Sampling},
+    regions::{
+        tess, AntiAlias, CompoundPath, FillRule, Point, PointFloat, Polygon, Region, Transform,
+    },
+};
+
+/// Fills a polygon with a texture. Every pixel center is moved into the
+/// texture by `to_texture`, so the polygon can show any cropped, rotated,
+/// scaled or mirrored part of it.
+pub struct TexturedPolygon {
+    polygon: Polygon,
+    texture: Rc<Image>,
+    to_texture: Transform,
+    sampling: Sampling,
+    anti_alias: AntiAlias,
+}
+
+impl TexturedPolygon {
+    pub fn new(polygon: Polygon, texture: Rc<Image>, to_texture: Transform) -> Self {
+        Self {
+            polygon,
+            texture,
+            to_texture,
+            sampling: Sampling::Nearest,
+            anti_alias: AntiAlias::None,
+        }
+    }
+
+    /// A triangle showing the part of the texture between the given texture
+    /// coordinates, in pixels of the texture, one for each vertex.
+    pub fn triangle(vertices: [PointFloat; 3], uvs: [PointFloat; 3], texture: Rc<Image>) -> Self {
+        let polygon = Polygon::float_triangle(vertices[0], vertices[1], vertices[2]);
+        // a triangle that lies on a line covers no pixels, so any transform
+        // will do for it
+        let to_texture = Transform::from_triangles(vertices, uvs).unwrap_or_default();
+        Self::new(polygon, texture, to_texture)
+    }
+
+    /// A polygon with the given vertices, showing the part of the texture
+    /// between the texture coordinates at every vertex, in pixels of the
+    /// texture. The texture is fitted to the first three vertices that don't
+    /// lie on a line, so the other coordinates should follow the same
+    /// scaling, rotation and skew. Panics if there isn't one texture
+    /// coordinate per vertex.
+    pub fn from_uvs(vertices: &[PointFloat], uvs: &[PointFloat], texture: Rc<Image>) -> Self {
+        assert_eq!(
+            vertices.len(),
+            uvs.len(),
+            "every vertex needs its own texture coordinate"
+        );
+        let polygon = Polygon::from_path(CompoundPath::new(
+            vec![vertices.to_vec()],
+            FillRule::NonZero,
+        ));
+        let to_texture = Transform::from_points(vertices, uvs).unwrap_or_default();
+        Self::new(polygon, texture, to_texture)
+    }
+
+    pub fn sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn anti_alias(mut self, anti_alias: AntiAlias) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+}
+
+impl Region for TexturedPolygon {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        let polygon = image.on_canvas(std::slice::from_ref(&self.polygon));
+        let to_texture = image
+            .to_canvas()
+            .invert()
+            .unwrap_or_default()
+            .then(&self.to_texture);
+        for (point, coverage) in polygon[0].iter_coverage(self.anti_alias) {
+            if !image.contains(&point) {
+                continue;
+            }
+            let uv = to_texture.apply(&PointFloat::pixel_center(&point));
+            let color = self.texture.sample(&uv, self.sampling);
+            mutations.push((point.with_coverage(coverage), color.with_coverage(coverage)));
+        }
+    }
+}
+
+/// Fills every polygon of a tessellation with its own part of one texture.
+pub struct TexturedTessellationTF {
+    tiles: Vec<TexturedPolygon>,
+}
+
+impl TexturedTessellationTF {
+    /// `to_texture` holds the transform into the texture for each polygon of
+    /// the tessellation, in the same order. Panics if there isn't exactly one
+    /// transform per polygon.
+    pub fn new(
+        tessellation: tess::PolygonTessellation,
+        texture: Rc<Image>,
+        to_texture: Vec<Transform>,
+        sampling: Sampling,
+    ) -> Self {
+        assert_eq!(
+            tessellation.polygons.len(),
+            to_texture.len(),
+            "every polygon needs its own transform into the texture"
+        );
+        let tiles = tessellation
+            .polygons
+            .into_iter()
+            .zip(to_texture)
+            .map(|(polygon, transform)| {
+                TexturedPolygon::new(polygon, texture.clone(), transform).sampling(sampling)
+            })
+            .collect();
+        Self { tiles }
+    }
+
+    /// `uvs` holds, for each polygon of the tessellation in the same order,
+    /// some of its vertices with the texture coordinates they show. Each
+    /// polygon is fitted to its first three vertices that don't lie on a
+    /// line. Panics if there isn't exactly one list per polygon.
+    pub fn from_uvs(
+        tessellation: tess::PolygonTessellation,
+        texture: Rc<Image>,
+        uvs: Vec<Vec<(PointFloat, PointFloat)>>,
+        sampling: Sampling,
+    ) -> Self {
+        let to_texture = uvs
+            .iter()
+            .map(|pairs| {
+                let (vertices, uvs): (Vec<PointFloat>, Vec<PointFloat>) =
+                    pairs.iter().copied().unzip();
+                Transform::from_points(&vertices, &uvs).unwrap_or_default()
+            })
+            .collect();
+        Self::new(tessellation, texture, to_texture, sampling)
+    }
+}
+
+impl Region for TexturedTessellationTF {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        for tile in self.tiles.iter() {
+            tile.get_mutations(image, mutations);
+        }
+    }
+}
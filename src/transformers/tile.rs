@@ -0,0 +1,127 @@
+use std::borrow::Cow;
+
+use crate::{
+    images::{ColorSpace, Image, Pixel},
+    regions::{AntiAlias, Point, Polygon, TileIterator, TilePoint, Transform},
+};
+
+/// A line along the inside of the edges of a tile, and an inset that
+/// shrinks every tile toward its centroid, leaving what is below showing in
+/// between, like the grout between tiles of a mosaic.
+#[derive(Clone, Copy, Debug)]
+pub struct TileBorder {
+    width: f32,
+    color: Pixel,
+    inset: f32,
+}
+
+impl TileBorder {
+    pub fn new() -> Self {
+        Self {
+            width: 0.0,
+            color: Pixel::new(),
+            inset: 0.0,
+        }
+    }
+
+    /// Draws a line of the given width and color along the inside of the
+    /// edges of every tile.
+    pub fn line(mut self, width: f32, color: Pixel) -> Self {
+        self.width = width.max(0.0);
+        self.color = color;
+        self
+    }
+
+    /// Shrinks every tile toward its centroid by the given fraction of its
+    /// size, leaving a gap between neighbours that shows what is below.
+    pub fn inset(mut self, inset: f32) -> Self {
+        self.inset = inset.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The border with its line widened by `scale`, for tiles drawn on a
+    /// supersampled canvas.
+    pub(super) fn scaled(&self, scale: f32) -> Self {
+        Self {
+            width: self.width * scale,
+            ..*self
+        }
+    }
+
+    /// The tiles shrunk by the inset, each toward its own centroid.
+    pub(super) fn inset_tiles<'a>(&self, polygons: &'a [Polygon]) -> Cow<'a, [Polygon]> {
+        if self.inset == 0.0 {
+            return Cow::Borrowed(polygons);
+        }
+        let scale = 1.0 - self.inset;
+        Cow::Owned(
+            polygons
+                .iter()
+                .map(|polygon| {
+                    polygon.transform(&Transform::scale(scale, scale).around(&polygon.centroid()))
+                })
+                .collect(),
+        )
+    }
+
+    /// The pixel of a tile filled with `fill`, with the line drawn over it.
+    /// Smooth lines cover pixels partly where their edge crosses them,
+    /// otherwise a pixel is on the line or not by its center.
+    pub fn shade(&self, tile: &TilePoint, fill: Pixel, smooth: bool) -> (Point, Pixel) {
+        let line = match (self.width > 0.0, smooth) {
+            (false, _) => 0.0,
+            (true, true) => (self.width - tile.edge_distance + 0.5).clamp(0.0, 1.0),
+            (true, false) => (tile.edge_distance <= self.width) as u8 as f32,
+        };
+        let color = if line <= 0.0 {
+            fill
+        } else if line >= 1.0 {
+            self.color
+        } else {
+            Pixel::mix(&[fill, self.color], &[1.0 - line, line], ColorSpace::Srgb)
+        };
+        (
+            tile.point.clone().with_coverage(tile.coverage),
+            color.with_coverage(tile.coverage),
+        )
+    }
+}
+
+impl Default for TileBorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fills every polygon with its own color from `colors`, with the border
+/// drawn over it, after moving them onto the canvas. Without a line the
+/// distance to the edges isn't needed, and the pixels are taken straight
+/// from the coverage of the polygons.
+pub(super) fn fill_tiles(
+    polygons: &[Polygon],
+    colors: &[Pixel],
+    anti_alias: AntiAlias,
+    border: &TileBorder,
+    image: &Image,
+    mutations: &mut Vec<(Point, Pixel)>,
+) {
+    let polygons = image.on_canvas(polygons);
+    let polygons = border.inset_tiles(&polygons);
+    let border = border.scaled(image.scale());
+    if border.width == 0.0 {
+        for (polygon, color) in polygons.iter().zip(colors) {
+            for (point, coverage) in polygon.iter_coverage(anti_alias) {
+                if image.contains(&point) {
+                    mutations.push((point.with_coverage(coverage), color.with_coverage(coverage)));
+                }
+            }
+        }
+        return;
+    }
+    let smooth = anti_alias != AntiAlias::None;
+    for tile in TileIterator::new(&polygons, anti_alias) {
+        if image.contains(&tile.point) {
+            mutations.push(border.shade(&tile, colors[tile.polygon], smooth));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use super::tile::TileBorder;
+use crate::{
+    images::{Image, Pixel},
+    regions::{tess, AntiAlias, Point, Region, TileIterator, TilePoint},
+};
+
+/// Gives the color of a pixel of a tile from its place in the tile.
+pub type TileShaderFn = Box<dyn Fn(&TilePoint) -> Pixel>;
+
+/// Colors every pixel of a tessellation with a shader, which can draw
+/// anything that follows the tiles, like per tile colors, bevels along the
+/// edges or gradients across each tile.
+pub struct TileShaderTF {
+    tessellation: tess::PolygonTessellation,
+    shader: TileShaderFn,
+    anti_alias: AntiAlias,
+    border: TileBorder,
+}
+
+impl TileShaderTF {
+    pub fn new(tessellation: tess::PolygonTessellation, shader: TileShaderFn) -> Self {
+        Self {
+            tessellation,
+            shader,
+            anti_alias: AntiAlias::None,
+            border: TileBorder::new(),
+        }
+    }
+
+    pub fn anti_alias(mut self, anti_alias: AntiAlias) -> Self {
+        self.anti_alias = anti_alias;
+        self
+    }
+
+    pub fn border(mut self, border: TileBorder) -> Self {
+        self.border = border;
+        self
+    }
+}
+
+impl Region for TileShaderTF {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        let smooth = self.anti_alias != AntiAlias::None;
+        let polygons = image.on_canvas(&self.tessellation.polygons);
+        let polygons = self.border.inset_tiles(&polygons);
+        let border = self.border.scaled(image.scale());
+        for tile in TileIterator::new(&polygons, self.anti_alias) {
+            if !image.contains(&tile.point) {
+                continue;
+            }
+            // the shader measures the distance to the edges in pixels of the
+            // output, like the rest of the layout
+            let fill = (self.shader)(&TilePoint {
+                edge_distance: tile.edge_distance / image.scale(),
+                ..tile.clone()
+            });
+            mutations.push(border.shade(&tile, fill, smooth));
+        }
+    }
+}
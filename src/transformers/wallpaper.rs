@@ -0,0 +1,261 @@
+use crate::{
+    images::{Image, Pixel},
+    regions::{Point, PointFloat, Region},
+};
+
+use super::motif::Motif;
+
+#[derive(Clone, Copy, Debug)]
+pub enum WallpaperGroup {
+    P1,
+    P2,
+    Pm,
+    Pg,
+    Cm,
+    Pmm,
+    Pmg,
+    Pgg,
+    Cmm,
+    P4,
+    P4m,
+    P4g,
+    P3,
+    P3m1,
+    P31m,
+    P6,
+    P6m,
+}
+
+/// An affine map in lattice coordinates:
+/// (x, y) -> (m[0] * x + m[1] * y + t[0], m[2] * x + m[3] * y + t[1])
+#[derive(Clone, Copy)]
+struct SymmetryOp {
+    m: [f32; 4],
+    t: [f32; 2],
+}
+
+const fn op(a: f32, b: f32, c: f32, d: f32) -> SymmetryOp {
+    SymmetryOp {
+        m: [a, b, c, d],
+        t: [0.0, 0.0],
+    }
+}
+
+const fn glide(a: f32, b: f32, c: f32, d: f32, tx: f32, ty: f32) -> SymmetryOp {
+    SymmetryOp {
+        m: [a, b, c, d],
+        t: [tx, ty],
+    }
+}
+
+const IDENTITY: SymmetryOp = op(1.0, 0.0, 0.0, 1.0);
+const ROT_180: SymmetryOp = op(-1.0, 0.0, 0.0, -1.0);
+const MIRROR_X: SymmetryOp = op(-1.0, 0.0, 0.0, 1.0);
+const MIRROR_Y: SymmetryOp = op(1.0, 0.0, 0.0, -1.0);
+
+// square lattice
+const ROT_90: SymmetryOp = op(0.0, -1.0, 1.0, 0.0);
+const ROT_270: SymmetryOp = op(0.0, 1.0, -1.0, 0.0);
+const MIRROR_DIAG: SymmetryOp = op(0.0, 1.0, 1.0, 0.0);
+const MIRROR_ANTI_DIAG: SymmetryOp = op(0.0, -1.0, -1.0, 0.0);
+
+// hexagonal lattice, with 120 degrees between the basis vectors
+const HEX_ROT_120: SymmetryOp = op(0.0, -1.0, 1.0, -1.0);
+const HEX_ROT_240: SymmetryOp = op(-1.0, 1.0, -1.0, 0.0);
+const HEX_ROT_60: SymmetryOp = op(1.0, -1.0, 1.0, 0.0);
+const HEX_ROT_300: SymmetryOp = op(0.0, 1.0, -1.0, 1.0);
+const HEX_MIRRORS_M1: [SymmetryOp; 3] = [
+    op(0.0, -1.0, -1.0, 0.0),
+    op(-1.0, 1.0, 0.0, 1.0),
+    op(1.0, 0.0, 1.0, -1.0),
+];
+const HEX_MIRRORS_1M: [SymmetryOp; 3] = [
+    op(0.0, 1.0, 1.0, 0.0),
+    op(1.0, -1.0, 0.0, -1.0),
+    op(-1.0, 0.0, -1.0, 1.0),
+];
+
+// the cell itself first, then every cell around it
+const NEIGHBOR_CELLS: [(f32, f32); 9] = [
+    (0.0, 0.0),
+    (-1.0, 0.0),
+    (1.0, 0.0),
+    (0.0, -1.0),
+    (0.0, 1.0),
+    (-1.0, -1.0),
+    (-1.0, 1.0),
+    (1.0, -1.0),
+    (1.0, 1.0),
+];
+
+impl SymmetryOp {
+    fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            self.m[0] * x + self.m[1] * y + self.t[0],
+            self.m[2] * x + self.m[3] * y + self.t[1],
+        )
+    }
+
+    fn shifted(mut self, tx: f32, ty: f32) -> Self {
+        self.t[0] += tx;
+        self.t[1] += ty;
+        self
+    }
+}
+
+fn centered(ops: &[SymmetryOp]) -> Vec<SymmetryOp> {
+    ops.iter()
+        .cloned()
+        .chain(ops.iter().map(|op| op.shifted(0.5, 0.5)))
+        .collect()
+}
+
+impl WallpaperGroup {
+    fn operations(&self) -> Vec<SymmetryOp> {
+        match self {
+            Self::P1 => vec![IDENTITY],
+            Self::P2 => vec![IDENTITY, ROT_180],
+            Self::Pm => vec![IDENTITY, MIRROR_X],
+            Self::Pg => vec![IDENTITY, MIRROR_X.shifted(0.0, 0.5)],
+            Self::Cm => centered(&[IDENTITY, MIRROR_X]),
+            Self::Pmm => vec![IDENTITY, ROT_180, MIRROR_X, MIRROR_Y],
+            Self::Pmg => vec![
+                IDENTITY,
+                ROT_180,
+                MIRROR_X.shifted(0.5, 0.0),
+                MIRROR_Y.shifted(0.5, 0.0),
+            ],
+            Self::Pgg => vec![
+                IDENTITY,
+                ROT_180,
+                MIRROR_X.shifted(0.5, 0.5),
+                MIRROR_Y.shifted(0.5, 0.5),
+            ],
+            Self::Cmm => centered(&[IDENTITY, ROT_180, MIRROR_X, MIRROR_Y]),
+            Self::P4 => vec![IDENTITY, ROT_90, ROT_180, ROT_270],
+            Self::P4m => vec![
+                IDENTITY,
+                ROT_90,
+                ROT_180,
+                ROT_270,
+                MIRROR_X,
+                MIRROR_Y,
+                MIRROR_DIAG,
+                MIRROR_ANTI_DIAG,
+            ],
+            Self::P4g => vec![
+                IDENTITY,
+                ROT_90,
+                ROT_180,
+                ROT_270,
+                MIRROR_X.shifted(0.5, 0.5),
+                MIRROR_Y.shifted(0.5, 0.5),
+                MIRROR_DIAG.shifted(0.5, 0.5),
+                MIRROR_ANTI_DIAG.shifted(0.5, 0.5),
+            ],
+            Self::P3 => vec![IDENTITY, HEX_ROT_120, HEX_ROT_240],
+            Self::P3m1 => [IDENTITY, HEX_ROT_120, HEX_ROT_240]
+                .into_iter()
+                .chain(HEX_MIRRORS_M1)
+                .collect(),
+            Self::P31m => [IDENTITY, HEX_ROT_120, HEX_ROT_240]
+                .into_iter()
+                .chain(HEX_MIRRORS_1M)
+                .collect(),
+            Self::P6 => vec![
+                IDENTITY,
+                HEX_ROT_60,
+                HEX_ROT_120,
+                ROT_180,
+                HEX_ROT_240,
+                HEX_ROT_300,
+            ],
+            Self::P6m => [
+                IDENTITY,
+                HEX_ROT_60,
+                HEX_ROT_120,
+                ROT_180,
+                HEX_ROT_240,
+                HEX_ROT_300,
+            ]
+            .into_iter()
+            .chain(HEX_MIRRORS_M1)
+            .chain(HEX_MIRRORS_1M)
+            .collect(),
+        }
+    }
+
+    /// Returns the lattice basis vectors for a cell of the given size. Square
+    /// and hexagonal groups only use the width of the cell.
+    fn lattice(&self, cell_size: &Point) -> [(f32, f32); 2] {
+        let w = cell_size.x as f32;
+        let h = cell_size.y as f32;
+        match self {
+            Self::P1 | Self::P2 => [(w, 0.0), (w / 4.0, h)],
+            Self::Pm | Self::Pg | Self::Cm | Self::Pmm | Self::Pmg | Self::Pgg | Self::Cmm => {
+                [(w, 0.0), (0.0, h)]
+            }
+            Self::P4 | Self::P4m | Self::P4g => [(w, 0.0), (0.0, w)],
+            Self::P3 | Self::P3m1 | Self::P31m | Self::P6 | Self::P6m => {
+                [(w, 0.0), (-w / 2.0, w * 3f32.sqrt() / 2.0)]
+            }
+        }
+    }
+}
+
+/// Repeats a motif across the canvas with the symmetries of a wallpaper
+/// group. The motif is drawn in cell coordinates, with the cell spanned by
+/// the group's lattice vectors from the origin; for the hexagonal groups the
+/// second vector points up and to the left. Anything the motif draws outside
+/// of the cell is wrapped into it.
+pub struct WallpaperTF {
+    motif: Box<dyn Region>,
+    group: WallpaperGroup,
+    cell_size: Point,
+}
+
+impl WallpaperTF {
+    pub fn new(motif: Box<dyn Region>, group: WallpaperGroup, cell_size: Point) -> Self {
+        Self {
+            motif,
+            group,
+            cell_size,
+        }
+    }
+}
+
+impl Region for WallpaperTF {
+    fn get_mutations(&self, image: &Image, mutations: &mut Vec<(Point, Pixel)>) {
+        let motif = Motif::render(self.motif.as_ref(), image);
+        let operations = self.group.operations();
+        let [a, b] = self.group.lattice(&self.cell_size);
+        let det = a.0 * b.1 - a.1 * b.0;
+
+        // the lattice is laid out in pixels of the output, while the motif
+        // and the pixels being filled are on the canvas
+        let scale = image.scale();
+        let to_lattice = |x: f32, y: f32| {
+            let (x, y) = (x / scale, y / scale);
+            ((x * b.1 - y * b.0) / det, (y * a.0 - x * a.1) / det)
+        };
+        let to_pixel = |u: f32, v: f32| ((u * a.0 + v * b.0) * scale, (u * a.1 + v * b.1) * scale);
+
+        for y in 0..image.size.y {
+            for x in 0..image.size.x {
+                let center = PointFloat::pixel_center(&Point::new(x, y));
+                let (u, v) = to_lattice(center.x, center.y);
+                let found = operations.iter().find_map(|op| {
+                    let (u, v) = op.apply(u, v);
+                    let (u, v) = (u.rem_euclid(1.0), v.rem_euclid(1.0));
+                    NEIGHBOR_CELLS.iter().find_map(|(du, dv)| {
+                        let (px, py) = to_pixel(u + du, v + dv);
+                        motif.get(px, py)
+                    })
+                });
+                if let Some(pixel) = found {
+                    mutations.push((Point::new(x, y), pixel));
+                }
+            }
+        }
+    }
+}